@@ -0,0 +1,311 @@
+//! Derive macro for the
+//! [error-accumulator](https://docs.rs/error-accumulator) crate.
+//!
+//! Use it via the `derive` feature of `error-accumulator`, see [`Accumulate`].
+
+#![deny(missing_debug_implementations)]
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    Data, DeriveInput, Field, Fields, GenericArgument, LitStr, Path, PathArguments, Type,
+    ext::IdentExt, parse_macro_input, spanned::Spanned,
+};
+
+/// Derive `error_accumulator::accumulate::Accumulate` for a struct with named
+/// fields.
+///
+/// The generated parser records one result per field of the struct. The name
+/// of each field is used for the recorded `SourcePath`s so they are always in
+/// sync with the struct's definition.
+///
+/// # Struct attributes
+///
+/// - `#[accumulate(from = RawType)]` (required): the raw type to parse from.
+///   It must have a field of the same name for each field of the struct.
+///
+/// Structs with more than 12 fields require the `large-tuples` feature of
+/// `error-accumulator`.
+///
+/// # Field attributes
+///
+/// - `#[accumulate(with = path::to::func)]`: parse the raw field with
+///   `func(raw) -> Result<T, E>`. Without this attribute
+///   [`TryInto`](core::convert::TryInto) is used.
+/// - `#[accumulate(nested)]`: the field's type implements `Accumulate` itself
+///   and is parsed as a nested struct.
+/// - `#[accumulate(each)]`: the field is a `Vec` whose elements are parsed
+///   one by one. Can be combined with `with` or `nested` which are then
+///   applied to the elements.
+/// - `#[accumulate(rename = "name")]`: use another name in the recorded
+///   paths.
+///
+/// # Example
+///
+/// ```
+/// use std::num::{NonZeroU16, ParseIntError};
+///
+/// use error_accumulator::accumulate::Accumulate;
+///
+/// struct RawHost {
+///     name: String,
+///     port: u32,
+/// }
+///
+/// struct RawConfig {
+///     retries: String,
+///     hosts: Vec<RawHost>,
+/// }
+///
+/// #[derive(Debug, Accumulate)]
+/// #[accumulate(from = RawHost)]
+/// struct Host {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Debug, Accumulate)]
+/// #[accumulate(from = RawConfig)]
+/// struct Config {
+///     #[accumulate(with = parse_retries)]
+///     retries: NonZeroU16,
+///     #[accumulate(each, nested)]
+///     hosts: Vec<Host>,
+/// }
+///
+/// fn parse_retries(raw: String) -> Result<NonZeroU16, ParseIntError> {
+///     raw.parse()
+/// }
+///
+/// let error = Config::accumulate(RawConfig {
+///     retries: "zero".to_string(),
+///     hosts: vec![RawHost {
+///         name: "localhost".to_string(),
+///         port: 100_000,
+///     }],
+/// })
+/// .unwrap_err();
+///
+/// assert_eq!(error.len(), 2);
+/// assert!(error.to_string().contains("hosts[0].port"));
+/// ```
+#[proc_macro_derive(Accumulate, attributes(accumulate))]
+pub fn derive_accumulate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct FieldOptions {
+    with: Option<Path>,
+    nested: bool,
+    each: bool,
+    rename: Option<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let raw = parse_struct_options(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`Accumulate` can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`Accumulate` can only be derived for structs",
+            ));
+        }
+    };
+
+    let steps = fields
+        .iter()
+        .map(expand_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("fields are named"))
+        .collect::<Vec<_>>();
+    let args = idents
+        .iter()
+        .map(|ident| format_ident!("__{}", ident.unraw()))
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::error_accumulator::accumulate::Accumulate for #name #ty_generics
+        #where_clause
+        {
+            type Raw = #raw;
+
+            fn accumulate_struct<__Parent>(
+                builder: ::error_accumulator::accumulate::EmptyStructBuilder<__Parent, Self>,
+                raw: Self::Raw,
+            ) -> <__Parent as ::error_accumulator::builder::ErrorBuilderParent<Self>>::AfterRecord
+            where
+                __Parent: ::error_accumulator::builder::ErrorBuilderParent<Self>,
+            {
+                #( let builder = #steps; )*
+                builder
+                    .on_ok(| #( #args: #types ),* | Self { #( #idents: #args ),* })
+                    .finish()
+            }
+        }
+    })
+}
+
+fn parse_struct_options(input: &DeriveInput) -> syn::Result<Type> {
+    let mut from = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("accumulate"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("from") {
+                from = Some(meta.value()?.parse::<Type>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `from`"))
+            }
+        })?;
+    }
+
+    from.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "missing `#[accumulate(from = RawType)]` attribute",
+        )
+    })
+}
+
+fn parse_field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        with: None,
+        nested: false,
+        each: false,
+        rename: None,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("accumulate"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("with") {
+                options.with = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("nested") {
+                options.nested = true;
+            } else if meta.path.is_ident("each") {
+                options.each = true;
+            } else if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error(
+                    "unsupported attribute, expected one of `with`, `nested`, `each`, or `rename`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+
+    if options.nested && options.with.is_some() {
+        return Err(syn::Error::new(
+            field.span(),
+            "`nested` and `with` can not be combined",
+        ));
+    }
+
+    Ok(options)
+}
+
+fn expand_field(field: &Field) -> syn::Result<TokenStream2> {
+    let options = parse_field_options(field)?;
+    let ident = field.ident.as_ref().expect("fields are named");
+    let ty = &field.ty;
+    let name = options
+        .rename
+        .clone()
+        .unwrap_or_else(|| LitStr::new(&ident.unraw().to_string(), ident.span()));
+    let field_name = quote! { ::error_accumulator::path::FieldName::new_unchecked(#name) };
+    let convert = converter(&options);
+
+    let step = if options.each {
+        let element = vec_element(ty)?;
+        if options.nested {
+            quote_spanned! {ty.span()=>
+                builder
+                    .array::<#element>(#field_name)
+                    .of_structs(raw.#ident, |builder, raw| {
+                        <#element as ::error_accumulator::accumulate::Accumulate>::accumulate_struct(
+                            builder,
+                            raw,
+                        )
+                    })
+                    .finish()
+            }
+        } else {
+            quote_spanned! {ty.span()=>
+                builder
+                    .array::<#element>(#field_name)
+                    .of_values(::core::iter::IntoIterator::into_iter(raw.#ident).map(#convert))
+                    .finish()
+            }
+        }
+    } else if options.nested {
+        quote_spanned! {ty.span()=>
+            <#ty as ::error_accumulator::accumulate::Accumulate>::accumulate_struct(
+                builder.strukt::<#ty>(#field_name),
+                raw.#ident,
+            )
+        }
+    } else {
+        quote_spanned! {ty.span()=>
+            builder.field::<#ty, _>(#field_name, (#convert)(raw.#ident))
+        }
+    };
+
+    Ok(step)
+}
+
+fn converter(options: &FieldOptions) -> TokenStream2 {
+    match &options.with {
+        Some(with) => quote! { #with },
+        None => quote! { ::core::convert::TryInto::try_into },
+    }
+}
+
+/// Extract `T` from `Vec<T>`.
+fn vec_element(ty: &Type) -> syn::Result<Type> {
+    let error = || syn::Error::new(ty.span(), "`each` can only be used on `Vec<T>` fields");
+
+    let Type::Path(path) = ty else {
+        return Err(error());
+    };
+    let segment = path.path.segments.last().ok_or_else(error)?;
+    if segment.ident != "Vec" {
+        return Err(error());
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(error());
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(element)) if args.args.len() == 1 => Ok(element.clone()),
+        _ => Err(error()),
+    }
+}
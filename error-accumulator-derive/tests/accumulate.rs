@@ -0,0 +1,131 @@
+use std::num::{NonZeroU16, ParseIntError, TryFromIntError};
+
+use error_accumulator::{
+    accumulate::Accumulate,
+    path::{FieldName, PathSegment, SourcePath},
+};
+
+struct RawHost {
+    url: String,
+    port: u32,
+}
+
+struct RawConfig {
+    name: String,
+    retries: String,
+    primary: RawHost,
+    hosts: Vec<RawHost>,
+    weights: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Accumulate)]
+#[accumulate(from = RawHost)]
+struct Host {
+    url: String,
+    #[accumulate(rename = "host_port")]
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Eq, Accumulate)]
+#[accumulate(from = RawConfig)]
+struct Config {
+    name: String,
+    #[accumulate(with = parse_retries)]
+    retries: NonZeroU16,
+    #[accumulate(nested)]
+    primary: Host,
+    #[accumulate(each, nested)]
+    hosts: Vec<Host>,
+    #[accumulate(each, with = parse_weight)]
+    weights: Vec<u8>,
+}
+
+fn parse_retries(raw: String) -> Result<NonZeroU16, ParseIntError> {
+    raw.parse()
+}
+
+fn parse_weight(raw: String) -> Result<u8, ParseIntError> {
+    raw.parse()
+}
+
+fn n(name: &str) -> FieldName {
+    name.parse().unwrap()
+}
+
+fn raw_host(port: u32) -> RawHost {
+    RawHost {
+        url: "localhost".to_string(),
+        port,
+    }
+}
+
+#[test]
+fn should_parse_valid_input() {
+    let config = Config::accumulate(RawConfig {
+        name: "test".to_string(),
+        retries: "3".to_string(),
+        primary: raw_host(80),
+        hosts: vec![raw_host(8080), raw_host(8081)],
+        weights: vec!["1".to_string(), "2".to_string()],
+    })
+    .unwrap();
+
+    assert_eq!(config.retries.get(), 3);
+    assert_eq!(config.primary.port, 80);
+    assert_eq!(
+        config
+            .hosts
+            .iter()
+            .map(|host| host.port)
+            .collect::<Vec<_>>(),
+        vec![8080, 8081]
+    );
+    assert_eq!(config.weights, vec![1, 2]);
+}
+
+#[test]
+fn should_record_errors_with_field_paths() {
+    let errors = Config::accumulate(RawConfig {
+        name: "test".to_string(),
+        retries: "0".to_string(),
+        primary: raw_host(100_000),
+        hosts: vec![raw_host(8080), raw_host(100_000)],
+        weights: vec!["1".to_string(), "x".to_string()],
+    })
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(
+        errors
+            .get_by_path(&SourcePath::new().join(PathSegment::field(n("retries"))))
+            .count(),
+        1
+    );
+    assert_eq!(
+        errors
+            .get_by_path(
+                &SourcePath::new()
+                    .join(PathSegment::field(n("primary")))
+                    .join(PathSegment::field(n("host_port")))
+            )
+            .count(),
+        1
+    );
+    assert_eq!(
+        errors
+            .get_by_path(
+                &SourcePath::new()
+                    .join(PathSegment::array(n("hosts"), 1))
+                    .join(PathSegment::field(n("host_port")))
+            )
+            .count(),
+        1
+    );
+    assert_eq!(
+        errors
+            .get_by_path(&SourcePath::new().join(PathSegment::array(n("weights"), 1)))
+            .count(),
+        1
+    );
+    assert_eq!(errors.get_by_type::<TryFromIntError>().count(), 2);
+}
@@ -110,15 +110,15 @@ mod no_acc {
 }
 
 mod accumulate {
-    use error_accumulator::{ErrorAccumulator, error::AccumulatedError, path::FieldName};
+    use error_accumulator::{ErrorAccumulator, error::AccumulatedError, field, path::FieldName};
     use reqwest::{StatusCode, Url};
 
     use crate::{Config, Host, RawConfig};
 
-    const INTERVAL: FieldName = FieldName::new_unchecked("interval");
-    const HOSTS: FieldName = FieldName::new_unchecked("hosts");
-    const URL: FieldName = FieldName::new_unchecked("url");
-    const EXPECTED_STATUS: FieldName = FieldName::new_unchecked("expected_status");
+    const INTERVAL: FieldName = field!("interval");
+    const HOSTS: FieldName = field!("hosts");
+    const URL: FieldName = field!("url");
+    const EXPECTED_STATUS: FieldName = field!("expected_status");
 
     pub fn parse(raw: RawConfig) -> Result<Config, AccumulatedError> {
         ErrorAccumulator::new()
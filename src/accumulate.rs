@@ -0,0 +1,96 @@
+//! Provide the [`Accumulate`] trait to reuse parsers of input types.
+
+use crate::{
+    ErrorAccumulator,
+    builder::{ErrorBuilderParent, StructBuilder},
+    cons::Nil,
+    error::AccumulatedError,
+    path::SourcePath,
+};
+
+/// Derive [`Accumulate`] for a validated struct.
+///
+/// See the `error-accumulator-derive` crate for the supported attributes.
+#[cfg(feature = "derive")]
+pub use error_accumulator_derive::Accumulate;
+
+/// A [`StructBuilder`] that has not recorded any values yet.
+///
+/// This is the builder [`Accumulate::accumulate_struct()`] starts with.
+pub type EmptyStructBuilder<Parent, Value> = StructBuilder<Parent, Value, Nil>;
+
+/// Types that can be parsed from a raw input type while accumulating errors.
+///
+/// Implementing this trait allows to reuse the parser of a type, e.g. when it
+/// is nested in other structs or arrays. Instead of writing the
+/// implementation by hand it can be derived with `#[derive(Accumulate)]` when
+/// the `derive` feature is enabled.
+///
+/// ```
+/// # use std::num::{NonZeroU16, TryFromIntError};
+/// # use error_accumulator::{
+/// #     accumulate::{Accumulate, EmptyStructBuilder},
+/// #     builder::ErrorBuilderParent,
+/// #     field,
+/// #     path::FieldName,
+/// # };
+/// const PORT: FieldName = field!("port");
+///
+/// struct RawServer {
+///     port: u32,
+/// }
+///
+/// #[derive(Debug)]
+/// struct Server {
+///     port: NonZeroU16,
+/// }
+///
+/// impl Accumulate for Server {
+///     type Raw = RawServer;
+///
+///     fn accumulate_struct<Parent>(
+///         builder: EmptyStructBuilder<Parent, Self>,
+///         raw: Self::Raw,
+///     ) -> Parent::AfterRecord
+///     where
+///         Parent: ErrorBuilderParent<Self>,
+///     {
+///         builder
+///             .field_builder(PORT)
+///             .value(u16::try_from(raw.port))
+///             .with_previous(|port: &u16| NonZeroU16::try_from(*port))
+///             .on_ok(|_, port| port)
+///             .finish()
+///             .on_ok(|port| Server { port })
+///             .finish()
+///     }
+/// }
+///
+/// let server = Server::accumulate(RawServer { port: 8080 }).unwrap();
+/// assert_eq!(server.port.get(), 8080);
+///
+/// let error = Server::accumulate(RawServer { port: 0 }).unwrap_err();
+/// assert_eq!(error.get_by_type::<TryFromIntError>().count(), 1);
+/// ```
+pub trait Accumulate: Sized {
+    /// The raw input type `Self` is parsed from.
+    type Raw;
+
+    /// Record all parsing results of `raw` with the provided `builder` and
+    /// finish it.
+    fn accumulate_struct<Parent>(
+        builder: EmptyStructBuilder<Parent, Self>,
+        raw: Self::Raw,
+    ) -> Parent::AfterRecord
+    where
+        Parent: ErrorBuilderParent<Self>;
+
+    /// Parse `raw` as the root of the input.
+    ///
+    /// Returns all accumulated errors if at least one error was recorded.
+    fn accumulate(raw: Self::Raw) -> Result<Self, AccumulatedError> {
+        let builder = StructBuilder::new(ErrorAccumulator::new(), SourcePath::new());
+        let (value,) = Self::accumulate_struct(builder, raw).analyse()?;
+        Ok(value)
+    }
+}
@@ -6,9 +6,17 @@ use crate::{AccumulatedError, Constructor, cons::ToTuple};
 
 mod array;
 mod field;
+mod map;
 mod strukt;
+mod variant;
 
-pub use self::{array::ArrayBuilder, field::FieldBuilder, strukt::StructBuilder};
+pub use self::{
+    array::ArrayBuilder,
+    field::FieldBuilder,
+    map::{MapBuilder, MapEntry},
+    strukt::StructBuilder,
+    variant::VariantBuilder,
+};
 
 /// Parent builders can have child builders to simulate nested structures.
 pub trait ErrorBuilderParent<T> {
@@ -20,7 +28,28 @@ pub trait ErrorBuilderParent<T> {
     type AfterRecord;
 
     /// Record the final result of the child builder.
+    ///
+    /// The child's result is only an error if it recorded at least one error
+    /// with [`Severity::Error`](crate::error::Severity::Error).
     fn finish_child_builder(self, child_result: Result<T, AccumulatedError>) -> Self::AfterRecord;
+
+    /// Record the non-fatal errors, e.g. warnings, of a child builder that
+    /// finished successfully.
+    ///
+    /// This is called right before
+    /// [`finish_child_builder()`](Self::finish_child_builder). By default the
+    /// warnings are dropped.
+    fn record_warnings(&mut self, warnings: AccumulatedError) {
+        let _ = warnings;
+    }
+
+    /// How many more errors the child builder may record before further
+    /// errors are suppressed, `None` if unlimited.
+    ///
+    /// See [`ErrorAccumulator::max_errors()`](crate::ErrorAccumulator::max_errors).
+    fn remaining_errors(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Intermediate state when either [`FieldBuilder::on_ok()`] or
@@ -45,12 +74,37 @@ where
     /// Finish the wrapped builder and pass the final result to the parent
     /// builder.
     pub fn finish(self) -> Parent::AfterRecord {
-        let result = if self.accumulated_errors.is_empty() {
-            Ok(self.constructor.construct(self.values.unwrap_tuple()))
-        } else {
-            Err(self.accumulated_errors)
-        };
+        let Self {
+            parent,
+            accumulated_errors,
+            values,
+            constructor,
+            _marker,
+        } = self;
 
-        self.parent.finish_child_builder(result)
+        finish_child(parent, accumulated_errors, || {
+            constructor.construct(values.unwrap_tuple())
+        })
+    }
+}
+
+/// Pass the final result of a child builder to its parent.
+///
+/// `value` is only called if no error with
+/// [`Severity::Error`](crate::error::Severity::Error) was recorded.
+pub(crate) fn finish_child<Parent, T>(
+    mut parent: Parent,
+    errors: AccumulatedError,
+    value: impl FnOnce() -> T,
+) -> Parent::AfterRecord
+where
+    Parent: ErrorBuilderParent<T>,
+{
+    if errors.has_errors() {
+        return parent.finish_child_builder(Err(errors));
+    }
+    if !errors.is_empty() {
+        parent.record_warnings(errors);
     }
+    parent.finish_child_builder(Ok(value()))
 }
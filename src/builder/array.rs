@@ -1,22 +1,23 @@
 use std::{error::Error, marker::PhantomData};
 
 use crate::{
-    builder::{ErrorBuilderParent, StructBuilder},
+    builder::{ErrorBuilderParent, StructBuilder, finish_child},
     cons::Nil,
-    error::AccumulatedError,
+    error::{AccumulatedError, Severity},
     path::{FieldName, PathSegment, SourcePath},
+    validators::{Validator, validate_value},
 };
 
 /// A builder to record the parsing results of elements of an array in the
 /// input.
 ///
-/// Arrays can be composed of single values or nested structs.
+/// Arrays can be composed of single values, nested structs or nested arrays.
 #[derive(Debug)]
 pub struct ArrayBuilder<Parent, Value> {
     parent: Parent,
     base: SourcePath,
     errors: AccumulatedError,
-    array_name: FieldName,
+    array_name: Option<FieldName>,
     values: Vec<Value>,
     _marker: PhantomData<Value>,
 }
@@ -25,11 +26,13 @@ impl<Parent, Value> ArrayBuilder<Parent, Value>
 where
     Parent: ErrorBuilderParent<Vec<Value>>,
 {
-    pub(crate) fn new(parent: Parent, base: SourcePath, field: FieldName) -> Self {
+    /// Elements of an array without a name, e.g. a top-level array, are
+    /// recorded with [`PathSegment::Index`].
+    pub(crate) fn new(parent: Parent, base: SourcePath, field: Option<FieldName>) -> Self {
         Self {
             base,
+            errors: AccumulatedError::with_limit(parent.remaining_errors()),
             parent,
-            errors: Default::default(),
             array_name: field,
             values: Default::default(),
             _marker: PhantomData,
@@ -37,14 +40,43 @@ where
     }
 
     /// Record an [`Iterator`] of parsing results for single values.
-    pub fn of_values<E>(self, values: impl IntoIterator<Item = Result<Value, E>>) -> Self
+    ///
+    /// The remaining elements are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_values<E>(mut self, values: impl IntoIterator<Item = Result<Value, E>>) -> Self
     where
         E: Error + Send + Sync + 'static,
     {
-        values
-            .into_iter()
-            .enumerate()
-            .fold(self, |rec, (index, result)| rec.value(index, result))
+        for (index, result) in values.into_iter().enumerate() {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            self = self.value(index, result);
+        }
+        self
+    }
+
+    /// Record an [`Iterator`] of values that are checked by a [`Validator`].
+    ///
+    /// All errors of the `validator` are recorded at the path of the element.
+    /// The remaining elements are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_validated<V>(mut self, values: impl IntoIterator<Item = Value>, validator: &V) -> Self
+    where
+        V: Validator<Value> + ?Sized,
+    {
+        for (index, value) in values.into_iter().enumerate() {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            let path = self.element_path(index);
+            if let Some(value) = validate_value(value, validator, &path, &mut self.errors) {
+                self.values.push(value);
+            }
+        }
+        self
     }
 
     /// Consume an [`Iterator`] of nested structs from the input recording
@@ -54,17 +86,22 @@ where
     /// the element that's passed into the parser as well. Use the
     /// `StructBuilder` to record any parsing results while processing the
     /// element.
-    pub fn of_structs<I, T, Parser>(self, elements: I, mut parse: Parser) -> Self
+    ///
+    /// The remaining elements are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_structs<I, T, Parser>(mut self, elements: I, mut parse: Parser) -> Self
     where
         I: IntoIterator<Item = T>,
         Parser: FnMut(StructBuilder<Self, Value, Nil>, T) -> Self,
     {
-        elements
-            .into_iter()
-            .enumerate()
-            .fold(self, |rec, (index, element)| {
-                parse(rec.strukt(index), element)
-            })
+        for (index, element) in elements.into_iter().enumerate() {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            self = parse(self.strukt(index), element);
+        }
+        self
     }
 
     /// Record a parsing results for a single value within the array at a
@@ -79,13 +116,30 @@ where
         match result {
             Ok(value) => self.values.push(value),
             Err(error) => {
-                self.errors.append(self.element_path(index), error);
+                self.errors.push(self.element_path(index), error);
             }
         }
 
         self
     }
 
+    /// Record a warning for the array.
+    ///
+    /// Warnings are reported alongside errors but don't make the array
+    /// invalid.
+    pub fn warn<W>(mut self, warning: W) -> Self
+    where
+        W: Error + Send + Sync + 'static,
+    {
+        let path = match &self.array_name {
+            Some(name) => self.base.join(PathSegment::Field(name.clone())),
+            None => self.base.clone(),
+        };
+        self.errors
+            .push_with_severity(path, Severity::Warning, warning);
+        self
+    }
+
     /// Start a [`StructBuilder`] to record the parsing results for a nested
     /// struct within the array at a certain index.
     pub fn strukt(self, index: usize) -> StructBuilder<Self, Value, Nil> {
@@ -96,23 +150,65 @@ where
     /// Finish the `ArrayBuilder` and pass the final result to the parent
     /// builder.
     pub fn finish(self) -> Parent::AfterRecord {
-        let result = if self.errors.is_empty() {
-            Ok(self.values)
-        } else {
-            Err(self.errors)
-        };
+        let Self {
+            parent,
+            errors,
+            values,
+            ..
+        } = self;
 
-        self.parent.finish_child_builder(result)
+        finish_child(parent, errors, || values)
     }
 
     fn element_path(&self, index: usize) -> SourcePath {
-        self.base.join(PathSegment::Array {
-            name: self.array_name.clone(),
-            index,
+        self.base.join(match &self.array_name {
+            Some(name) => PathSegment::Array {
+                name: name.clone(),
+                index,
+            },
+            None => PathSegment::Index(index),
         })
     }
 }
 
+impl<Parent, Element> ArrayBuilder<Parent, Vec<Element>>
+where
+    Parent: ErrorBuilderParent<Vec<Vec<Element>>>,
+{
+    /// Consume an [`Iterator`] of nested arrays from the input recording
+    /// errors while parsing.
+    ///
+    /// The provided `Parser` is a closure that receives an [`ArrayBuilder`]
+    /// for the element that's passed into the parser as well.
+    ///
+    /// The remaining elements are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_arrays<I, T, Parser>(mut self, elements: I, mut parse: Parser) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        Parser: FnMut(ArrayBuilder<Self, Element>, T) -> Self,
+    {
+        for (index, element) in elements.into_iter().enumerate() {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            self = parse(self.array(index), element);
+        }
+        self
+    }
+
+    /// Start an [`ArrayBuilder`] to record the parsing results for a nested
+    /// array within the array at a certain index.
+    ///
+    /// Errors are recorded with both indices in the path, e.g.
+    /// `matrix[1][2]`.
+    pub fn array(self, index: usize) -> ArrayBuilder<Self, Element> {
+        let path = self.element_path(index);
+        ArrayBuilder::new(self, path, None)
+    }
+}
+
 impl<Parent, Value> ErrorBuilderParent<Value> for ArrayBuilder<Parent, Value> {
     type AfterRecord = Self;
 
@@ -129,6 +225,14 @@ impl<Parent, Value> ErrorBuilderParent<Value> for ArrayBuilder<Parent, Value> {
 
         self
     }
+
+    fn record_warnings(&mut self, warnings: AccumulatedError) {
+        self.errors.merge(warnings);
+    }
+
+    fn remaining_errors(&self) -> Option<usize> {
+        self.errors.remaining()
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +243,35 @@ mod tests {
     #[derive(Debug, PartialEq, Eq)]
     struct Test(u32);
 
+    #[test]
+    fn should_record_nested_arrays() {
+        let res = ErrorAccumulator::new()
+            .array::<Vec<u32>>(n("matrix"))
+            .of_arrays([["1", "2"], ["3", "x"]], |rec, row| {
+                rec.of_values(row.map(str::parse)).finish()
+            })
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res.get_by_path(&"matrix[1][1]".parse().unwrap()).count(), 1);
+    }
+
+    #[test]
+    fn should_record_top_level_array() {
+        let (res,) = ErrorAccumulator::new()
+            .elements::<Vec<u32>>()
+            .array(0)
+            .of_values(["1", "2"].map(str::parse))
+            .finish()
+            .finish()
+            .analyse()
+            .unwrap();
+
+        assert_eq!(res, vec![vec![1, 2]]);
+    }
+
     #[test]
     fn should_record_array_of_structs() {
         let (res,) = ErrorAccumulator::new()
@@ -153,10 +286,64 @@ mod tests {
         assert_eq!(vec![Test(42), Test(21), Test(33)], res);
     }
 
+    #[test]
+    fn should_stop_after_max_errors() {
+        let mut parsed = 0;
+        let res = ErrorAccumulator::new()
+            .max_errors(2)
+            .field(n("name"), "x".parse::<u32>())
+            .array(n("foo"))
+            .of_structs(["1", "a", "b", "c", "d"], |rec, value| {
+                parsed += 1;
+                rec.field_builder(n("num"))
+                    .value(value.parse::<u32>())
+                    .value(value.parse::<u64>())
+                    .on_ok(|num, _| Test(num))
+                    .finish()
+                    .on_ok(|test| test)
+                    .finish()
+            })
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(parsed, 2);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res.suppressed(), 1);
+        assert!(
+            res.to_string()
+                .ends_with("- ... and at least 1 more error was suppressed\n")
+        );
+    }
+
+    #[test]
+    fn should_stop_consuming_values_after_max_errors() {
+        let mut parsed = 0;
+        let res = ErrorAccumulator::new()
+            .max_errors(3)
+            .array::<u32>(n("ids"))
+            .of_values((0..100_000).map(|_| {
+                parsed += 1;
+                "x".parse()
+            }))
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(parsed, 4);
+        assert_eq!(res.len(), 3);
+        assert_eq!(res.suppressed(), 0);
+        assert!(res.is_truncated());
+        assert!(
+            res.to_string()
+                .ends_with("- ... further errors were suppressed\n")
+        );
+    }
+
     #[test]
     fn should_record_array_of_values() {
         let (res,) = ErrorAccumulator::new()
-            .array(n("foo"))
+            .array::<i32>(n("foo"))
             .of_values(vec!["42".parse(), "21".parse(), "33".parse()])
             .finish()
             .analyse()
@@ -1,12 +1,13 @@
-use std::{error::Error, marker::PhantomData};
+use std::{borrow::Cow, error::Error, marker::PhantomData};
 
 use crate::{
     append_or_record,
-    builder::{BuilderFinisher, ErrorBuilderParent},
+    builder::{BuilderFinisher, ErrorBuilderParent, finish_child},
     cons::{Append, AsRefTuple, Cons, Nil, ToTuple},
     construct::{Constructor, ListValidator},
-    error::AccumulatedError,
+    error::{AccumulatedError, Coded, ErrorCode, Localize, Severity},
     path::SourcePath,
+    validators::{Validator, validate_value},
 };
 
 /// A builder to record parsing results for a field of the input.
@@ -26,8 +27,8 @@ where
     pub(crate) fn new(parent: Parent, path: SourcePath) -> Self {
         Self {
             field: path,
+            errors: AccumulatedError::with_limit(parent.remaining_errors()),
             parent,
-            errors: Default::default(),
             values: Nil,
             _marker: PhantomData,
         }
@@ -63,6 +64,63 @@ where
         }
     }
 
+    /// Record a parsing result for the field with the [code](ErrorCode) of its
+    /// error.
+    pub fn coded_value<T, E>(
+        self,
+        result: Result<T, E>,
+    ) -> FieldBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        E: ErrorCode + Send + Sync + 'static,
+    {
+        self.value(result.map_err(Coded::from_error))
+    }
+
+    /// Record a parsing result for the field with the [code](ErrorCode) and the
+    /// [message key](Localize) of its error.
+    pub fn localized_value<T, E>(
+        self,
+        result: Result<T, E>,
+    ) -> FieldBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        E: ErrorCode + Localize + Send + Sync + 'static,
+    {
+        self.value(result.map_err(Coded::localized))
+    }
+
+    /// Record a value for the field that is checked by a [`Validator`].
+    ///
+    /// All errors of the `validator` are recorded.
+    pub fn validated<T, V>(
+        self,
+        value: T,
+        validator: &V,
+    ) -> FieldBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        V: Validator<T> + ?Sized,
+    {
+        let Self {
+            parent,
+            mut errors,
+            field,
+            values,
+            _marker,
+        } = self;
+
+        let values = values.append(validate_value(value, validator, &field, &mut errors));
+
+        FieldBuilder {
+            parent,
+            errors,
+            field,
+            values,
+            _marker,
+        }
+    }
+
     /// Record a value for the field.
     ///
     /// This is infallible so it's easy to insert values that do not need
@@ -90,6 +148,39 @@ where
         }
     }
 
+    /// Record the error of a check of the field but discard its `Ok` value.
+    ///
+    /// Unlike [`with_previous()`](Self::with_previous) the check is recorded
+    /// even if errors were already recorded so several checks, e.g. of the
+    /// [`validators`](crate::validators), report all their errors.
+    pub fn check<T, E>(mut self, result: Result<T, E>) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        if let Err(error) = result {
+            self.errors.push(self.field.clone(), error);
+        }
+        self
+    }
+
+    /// Record the error of a check of the field with its [code](ErrorCode),
+    /// see [`check()`](Self::check).
+    pub fn check_coded<T, E>(self, result: Result<T, E>) -> Self
+    where
+        E: ErrorCode + Send + Sync + 'static,
+    {
+        self.check(result.map_err(Coded::from_error))
+    }
+
+    /// Record the error of a check of the field with its [code](ErrorCode) and
+    /// [message key](Localize), see [`check()`](Self::check).
+    pub fn check_localized<T, E>(self, result: Result<T, E>) -> Self
+    where
+        E: ErrorCode + Localize + Send + Sync + 'static,
+    {
+        self.check(result.map_err(Coded::localized))
+    }
+
     /// Run another validation step on the previously recorded `Ok` values if
     /// there were no errors yet.
     ///
@@ -114,7 +205,7 @@ where
             _marker,
         } = self;
 
-        let values = if errors.is_empty() {
+        let values = if !errors.has_errors() {
             let result = validator.validate(&values);
             append_or_record(values, &field, result, &mut errors)
         } else {
@@ -130,6 +221,39 @@ where
         }
     }
 
+    /// Record a warning for the field.
+    ///
+    /// Warnings are reported alongside errors but don't make the field
+    /// invalid.
+    pub fn warn<W>(mut self, warning: W) -> Self
+    where
+        W: Error + Send + Sync + 'static,
+    {
+        self.errors
+            .push_with_severity(self.field.clone(), Severity::Warning, warning);
+        self
+    }
+
+    /// Attach `code` to all errors recorded so far for the field that don't
+    /// have a code yet.
+    ///
+    /// ```
+    /// # use error_accumulator::{ErrorAccumulator, field};
+    /// let errors = ErrorAccumulator::new()
+    ///     .field_builder(field!("port"))
+    ///     .value("http".parse::<u16>())
+    ///     .code("E0042")
+    ///     .finish()
+    ///     .analyse()
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(errors.get_by_code("E0042").count(), 1);
+    /// ```
+    pub fn code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.errors.set_missing_codes(code.into());
+        self
+    }
+
     /// Provide a [`Constructor`] to convert the recorded `Ok` values into the
     /// target type.
     pub fn on_ok<C>(self, constructor: C) -> BuilderFinisher<Parent, Value, List, C>
@@ -154,14 +278,17 @@ where
     /// Finish the builder and pass the builder's final result to the parent
     /// builder.
     pub fn finish(self) -> Parent::AfterRecord {
-        let result = if self.errors.is_empty() {
-            let (value,) = self.values.unwrap_tuple();
-            Ok(value)
-        } else {
-            Err(self.errors)
-        };
+        let Self {
+            parent,
+            errors,
+            values,
+            ..
+        } = self;
 
-        self.parent.finish_child_builder(result)
+        finish_child(parent, errors, || {
+            let (value,) = values.unwrap_tuple();
+            value
+        })
     }
 }
 
@@ -169,7 +296,7 @@ where
 mod tests {
     use std::num::NonZeroI16;
 
-    use crate::{ErrorAccumulator, test_util::n};
+    use crate::{ErrorAccumulator, error::MissingField, test_util::n};
 
     #[test]
     fn should_allow_multivalue_field_record() {
@@ -185,6 +312,23 @@ mod tests {
         assert_eq!(num, 42);
     }
 
+    #[test]
+    fn should_attach_codes_of_coded_errors() {
+        let err = ErrorAccumulator::new()
+            .field_builder(n("foo"))
+            .coded_value(Err::<u32, _>(MissingField))
+            .check_coded(Err::<(), _>(MissingField))
+            .check(Err::<(), _>(MissingField))
+            .on_ok(|v| v)
+            .finish()
+            .coded_field(n("bar"), Err::<u32, _>(MissingField))
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(err.len(), 4);
+        assert_eq!(err.get_by_code("missing_field").count(), 3);
+    }
+
     #[test]
     fn should_return_error_on_multivalue_field_record() {
         let err = ErrorAccumulator::new()
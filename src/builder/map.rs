@@ -0,0 +1,380 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    builder::{ErrorBuilderParent, StructBuilder, finish_child},
+    cons::Nil,
+    error::{AccumulatedError, Severity},
+    path::{FieldName, PathSegment, SourcePath},
+};
+
+/// A builder to record the parsing results of entries of a map in the input.
+///
+/// The values of a map can be single values or nested structs. The recorded
+/// entries are collected into `Map`, e.g. a [`HashMap`](std::collections::HashMap)
+/// or a [`BTreeMap`](std::collections::BTreeMap).
+///
+/// Errors are recorded with the entry's key in the path, e.g.
+/// `hosts["api"].url`.
+#[derive(Debug)]
+pub struct MapBuilder<Parent, Map> {
+    parent: Parent,
+    base: SourcePath,
+    errors: AccumulatedError,
+    map_name: FieldName,
+    values: Map,
+}
+
+/// The parent of a [`StructBuilder`] that records the value of a single map
+/// entry.
+///
+/// See [`MapBuilder::strukt()`] for more details.
+#[derive(Debug)]
+pub struct MapEntry<Parent, Key> {
+    builder: Parent,
+    key: Option<Key>,
+}
+
+impl<Parent, Map> MapBuilder<Parent, Map>
+where
+    Parent: ErrorBuilderParent<Map>,
+    Map: Default,
+{
+    pub(crate) fn new(parent: Parent, base: SourcePath, field: FieldName) -> Self {
+        Self {
+            base,
+            errors: AccumulatedError::with_limit(parent.remaining_errors()),
+            parent,
+            map_name: field,
+            values: Default::default(),
+        }
+    }
+
+    /// Record an [`Iterator`] of keys and parsing results for single values.
+    ///
+    /// The remaining entries are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_values<Key, Value, E>(
+        mut self,
+        entries: impl IntoIterator<Item = (Key, Result<Value, E>)>,
+    ) -> Self
+    where
+        Key: Display,
+        Map: Extend<(Key, Value)>,
+        E: Error + Send + Sync + 'static,
+    {
+        for (key, result) in entries {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            self = self.value(key, result);
+        }
+        self
+    }
+
+    /// Record an [`Iterator`] of raw keys and parsing results for the keys and
+    /// single values.
+    ///
+    /// Errors of both, keys and values, are recorded under the raw key. The
+    /// remaining entries are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_entries<RawKey, Key, Value, KeyErr, ValueErr>(
+        mut self,
+        entries: impl IntoIterator<Item = (RawKey, Result<Key, KeyErr>, Result<Value, ValueErr>)>,
+    ) -> Self
+    where
+        RawKey: Display,
+        Map: Extend<(Key, Value)>,
+        KeyErr: Error + Send + Sync + 'static,
+        ValueErr: Error + Send + Sync + 'static,
+    {
+        for (raw_key, key, value) in entries {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            self = self.entry(raw_key, key, value);
+        }
+        self
+    }
+
+    /// Consume an [`Iterator`] of keys and nested structs from the input
+    /// recording errors while parsing.
+    ///
+    /// The provided `Parser` is a closure that receives a [`StructBuilder`] for
+    /// the entry's value that's passed into the parser as well. Use the
+    /// `StructBuilder` to record any parsing results while processing the
+    /// value.
+    ///
+    /// The remaining entries are skipped once the
+    /// [error limit](crate::ErrorAccumulator::max_errors) is reached.
+    pub fn of_structs<I, Key, T, Value, Parser>(mut self, entries: I, mut parse: Parser) -> Self
+    where
+        I: IntoIterator<Item = (Key, T)>,
+        Key: Display,
+        Map: Extend<(Key, Value)>,
+        Parser: FnMut(StructBuilder<MapEntry<Self, Key>, Value, Nil>, T) -> Self,
+    {
+        for (key, element) in entries {
+            if self.errors.is_exhausted() {
+                self.errors.set_truncated();
+                break;
+            }
+            self = parse(self.strukt(key), element);
+        }
+        self
+    }
+
+    /// Record a parsing result for a single value within the map with a
+    /// certain key.
+    ///
+    /// This is a low-level operation, consider using
+    /// [`of_values()`](Self::of_values) instead.
+    pub fn value<Key, Value, E>(mut self, key: Key, result: Result<Value, E>) -> Self
+    where
+        Key: Display,
+        Map: Extend<(Key, Value)>,
+        E: Error + Send + Sync + 'static,
+    {
+        match result {
+            Ok(value) => self.values.extend([(key, value)]),
+            Err(error) => {
+                let path = self.entry_path(&key);
+                self.errors.push(path, error);
+            }
+        }
+
+        self
+    }
+
+    /// Record parsing results for the key and the value of a single entry
+    /// within the map.
+    ///
+    /// This is a low-level operation, consider using
+    /// [`of_entries()`](Self::of_entries) instead.
+    pub fn entry<RawKey, Key, Value, KeyErr, ValueErr>(
+        mut self,
+        raw_key: RawKey,
+        key: Result<Key, KeyErr>,
+        value: Result<Value, ValueErr>,
+    ) -> Self
+    where
+        RawKey: Display,
+        Map: Extend<(Key, Value)>,
+        KeyErr: Error + Send + Sync + 'static,
+        ValueErr: Error + Send + Sync + 'static,
+    {
+        let path = self.entry_path(&raw_key);
+        match (key, value) {
+            (Ok(key), Ok(value)) => self.values.extend([(key, value)]),
+            (key, value) => {
+                if let Err(error) = key {
+                    self.errors.push(path.clone(), error);
+                }
+                if let Err(error) = value {
+                    self.errors.push(path, error);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Start a [`StructBuilder`] to record the parsing results for a nested
+    /// struct within the map with a certain key.
+    pub fn strukt<Key, Value>(self, key: Key) -> StructBuilder<MapEntry<Self, Key>, Value, Nil>
+    where
+        Key: Display,
+        Map: Extend<(Key, Value)>,
+    {
+        let path = self.entry_path(&key);
+        let entry = MapEntry {
+            builder: self,
+            key: Some(key),
+        };
+        StructBuilder::new(entry, path)
+    }
+
+    /// Start a [`StructBuilder`] to record the parsing results for a nested
+    /// struct within the map with a key that needs parsing as well.
+    ///
+    /// Errors of the key are recorded under the raw key.
+    pub fn strukt_entry<RawKey, Key, Value, E>(
+        mut self,
+        raw_key: RawKey,
+        key: Result<Key, E>,
+    ) -> StructBuilder<MapEntry<Self, Key>, Value, Nil>
+    where
+        RawKey: Display,
+        Map: Extend<(Key, Value)>,
+        E: Error + Send + Sync + 'static,
+    {
+        let path = self.entry_path(&raw_key);
+        let key = match key {
+            Ok(key) => Some(key),
+            Err(error) => {
+                self.errors.push(path.clone(), error);
+                None
+            }
+        };
+        let entry = MapEntry { builder: self, key };
+        StructBuilder::new(entry, path)
+    }
+
+    /// Record a warning for the map.
+    ///
+    /// Warnings are reported alongside errors but don't make the map invalid.
+    pub fn warn<W>(mut self, warning: W) -> Self
+    where
+        W: Error + Send + Sync + 'static,
+    {
+        let path = self.base.join(PathSegment::Field(self.map_name.clone()));
+        self.errors
+            .push_with_severity(path, Severity::Warning, warning);
+        self
+    }
+
+    /// Finish the `MapBuilder` and pass the final result to the parent
+    /// builder.
+    pub fn finish(self) -> Parent::AfterRecord {
+        let Self {
+            parent,
+            errors,
+            values,
+            ..
+        } = self;
+
+        finish_child(parent, errors, || values)
+    }
+
+    fn entry_path(&self, key: &impl Display) -> SourcePath {
+        self.base.join(PathSegment::Key {
+            name: self.map_name.clone(),
+            key: key.to_string(),
+        })
+    }
+}
+
+impl<Parent, Map, Key, Value> ErrorBuilderParent<Value> for MapEntry<MapBuilder<Parent, Map>, Key>
+where
+    Map: Extend<(Key, Value)>,
+{
+    type AfterRecord = MapBuilder<Parent, Map>;
+
+    fn finish_child_builder(
+        self,
+        child_result: Result<Value, AccumulatedError>,
+    ) -> Self::AfterRecord {
+        let Self { mut builder, key } = self;
+
+        match (key, child_result) {
+            (Some(key), Ok(value)) => builder.values.extend([(key, value)]),
+            // The key's error was already recorded.
+            (None, Ok(_)) => {}
+            (_, Err(errors)) => builder.errors.merge(errors),
+        }
+
+        builder
+    }
+
+    fn record_warnings(&mut self, warnings: AccumulatedError) {
+        self.builder.errors.merge(warnings);
+    }
+
+    fn remaining_errors(&self) -> Option<usize> {
+        self.builder.errors.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        num::{NonZeroU16, ParseIntError},
+    };
+
+    use super::*;
+    use crate::{ErrorAccumulator, test_util::n};
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Test(u32);
+
+    #[test]
+    fn should_record_map_of_values() {
+        let (res,) = ErrorAccumulator::new()
+            .map(n("foo"))
+            .of_values([("a", "42".parse::<u32>()), ("b", "21".parse())])
+            .finish()
+            .analyse()
+            .unwrap();
+
+        assert_eq!(BTreeMap::from([("a", 42), ("b", 21)]), res);
+    }
+
+    #[test]
+    fn should_record_map_of_structs() {
+        let (res,) = ErrorAccumulator::new()
+            .map::<HashMap<_, _>>(n("foo"))
+            .of_structs([("a", "42"), ("b", "21")], |rec, value| {
+                rec.field(n("num"), value.parse()).on_ok(Test).finish()
+            })
+            .finish()
+            .analyse()
+            .unwrap();
+
+        assert_eq!(HashMap::from([("a", Test(42)), ("b", Test(21))]), res);
+    }
+
+    #[test]
+    fn should_record_errors_under_key() {
+        let res = ErrorAccumulator::new()
+            .strukt(n("config"))
+            .map::<BTreeMap<NonZeroU16, Test>>(n("ports"))
+            .strukt_entry("0", "0".parse::<NonZeroU16>())
+            .field(n("num"), "1".parse())
+            .on_ok(Test)
+            .finish()
+            .strukt_entry("80", "80".parse::<NonZeroU16>())
+            .field(n("num"), "x".parse())
+            .on_ok(Test)
+            .finish()
+            .finish()
+            .on_ok(|ports| ports)
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(res.get_by_type::<ParseIntError>().count(), 2);
+        assert_eq!(
+            res.get_by_path(&"config.ports[\"0\"]".parse().unwrap())
+                .count(),
+            1
+        );
+        assert_eq!(
+            res.get_by_path(&"config.ports[\"80\"].num".parse().unwrap())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn should_validate_keys_and_values() {
+        let res = ErrorAccumulator::new()
+            .map::<HashMap<NonZeroU16, u32>>(n("foo"))
+            .of_entries([
+                ("0", "0".parse(), "1".parse::<u32>()),
+                ("1", "1".parse(), "x".parse()),
+                ("2", "2".parse(), "2".parse()),
+            ])
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(
+            res.get_by_path(&SourcePath::new().join(PathSegment::key(n("foo"), "1")))
+                .count(),
+            1
+        );
+    }
+}
@@ -1,17 +1,21 @@
-use std::{error::Error, marker::PhantomData};
+use std::{borrow::Cow, error::Error, marker::PhantomData};
 
 use crate::{
     append_or_record,
-    builder::{ArrayBuilder, BuilderFinisher, ErrorBuilderParent, FieldBuilder},
+    builder::{
+        ArrayBuilder, BuilderFinisher, ErrorBuilderParent, FieldBuilder, MapBuilder, VariantBuilder,
+    },
     cons::{Append, AsRefTuple, Nil, ToTuple},
     construct::{Constructor, ListValidator},
-    error::AccumulatedError,
+    error::{AccumulatedError, Coded, ErrorCode, Localize, MissingField, Severity},
     path::{FieldName, PathSegment, SourcePath},
+    validators::Validator,
 };
 
 /// A builder to record parsing results for a nested struct in the input.
 ///
-/// A `StructBuilder` can have other nested structs, arrays, and fields.
+/// A `StructBuilder` can have other nested structs, arrays, maps, tagged
+/// unions, and fields.
 #[derive(Debug)]
 pub struct StructBuilder<Parent, Value, List> {
     parent: Parent,
@@ -28,8 +32,8 @@ where
     pub(crate) fn new(parent: Parent, base: SourcePath) -> Self {
         Self {
             struct_path: base,
+            errors: AccumulatedError::with_limit(parent.remaining_errors()),
             parent,
-            errors: Default::default(),
             values: Nil,
             _marker: PhantomData,
         }
@@ -55,6 +59,112 @@ where
         FieldBuilder::new(self, field_path).value(result).finish()
     }
 
+    /// Record a parsing result for a field in this struct with the
+    /// [code](ErrorCode) of its error.
+    pub fn coded_field<T, E>(
+        self,
+        field: FieldName,
+        result: Result<T, E>,
+    ) -> StructBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        E: ErrorCode + Send + Sync + 'static,
+        Self: ErrorBuilderParent<T, AfterRecord = StructBuilder<Parent, Value, List::Output>>,
+    {
+        let field_path = self.struct_path.join(PathSegment::Field(field));
+        FieldBuilder::new(self, field_path)
+            .coded_value(result)
+            .finish()
+    }
+
+    /// Record a parsing result for a field in this struct with the
+    /// [code](ErrorCode) and the [message key](Localize) of its error.
+    pub fn localized_field<T, E>(
+        self,
+        field: FieldName,
+        result: Result<T, E>,
+    ) -> StructBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        E: ErrorCode + Localize + Send + Sync + 'static,
+        Self: ErrorBuilderParent<T, AfterRecord = StructBuilder<Parent, Value, List::Output>>,
+    {
+        let field_path = self.struct_path.join(PathSegment::Field(field));
+        FieldBuilder::new(self, field_path)
+            .localized_value(result)
+            .finish()
+    }
+
+    /// Record a value for a field in this struct that is checked by a
+    /// [`Validator`].
+    ///
+    /// See [`FieldBuilder::validated()`].
+    pub fn validated_field<T, V>(
+        self,
+        field: FieldName,
+        value: T,
+        validator: &V,
+    ) -> StructBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        V: Validator<T> + ?Sized,
+        Self: ErrorBuilderParent<T, AfterRecord = StructBuilder<Parent, Value, List::Output>>,
+    {
+        let field_path = self.struct_path.join(PathSegment::Field(field));
+        FieldBuilder::new(self, field_path)
+            .validated(value, validator)
+            .finish()
+    }
+
+    /// Record a parsing result for a field in this struct that must be present
+    /// in the input.
+    ///
+    /// If the field is missing, i.e. `result` is `None`, a [`MissingField`]
+    /// error is recorded.
+    pub fn required_field<T, E>(
+        self,
+        field: FieldName,
+        result: Option<Result<T, E>>,
+    ) -> StructBuilder<Parent, Value, List::Output>
+    where
+        List: Append<T>,
+        E: Error + Send + Sync + 'static,
+        Self: ErrorBuilderParent<T, AfterRecord = StructBuilder<Parent, Value, List::Output>>,
+    {
+        let field_path = self.struct_path.join(PathSegment::Field(field));
+        let builder = FieldBuilder::new(self, field_path);
+        match result {
+            Some(result) => builder.value(result).finish(),
+            None => builder
+                .value(Err::<T, _>(Coded::localized(MissingField)))
+                .finish(),
+        }
+    }
+
+    /// Record a parsing result for a field in this struct that may be missing
+    /// in the input.
+    ///
+    /// The recorded value is `None` if the field is missing, i.e. `result` is
+    /// `None`.
+    pub fn optional_field<T, E>(
+        self,
+        field: FieldName,
+        result: Option<Result<T, E>>,
+    ) -> StructBuilder<Parent, Value, <List as Append<Option<T>>>::Output>
+    where
+        List: Append<Option<T>>,
+        E: Error + Send + Sync + 'static,
+        Self: ErrorBuilderParent<
+                Option<T>,
+                AfterRecord = StructBuilder<Parent, Value, <List as Append<Option<T>>>::Output>,
+            >,
+    {
+        let field_path = self.struct_path.join(PathSegment::Field(field));
+        FieldBuilder::new(self, field_path)
+            .value(result.transpose())
+            .finish()
+    }
+
     /// Start a [`FieldBuilder`] to record the parsing results for a field in
     /// this struct.
     pub fn field_builder<FieldValue>(self, field: FieldName) -> FieldBuilder<Self, FieldValue, Nil>
@@ -82,7 +192,28 @@ where
         List: Append<Vec<ElementValue>>,
     {
         let base = self.struct_path.clone();
-        ArrayBuilder::new(self, base, field)
+        ArrayBuilder::new(self, base, Some(field))
+    }
+
+    /// Start a [`MapBuilder`] to record the parsing results for a nested map
+    /// within the current struct.
+    pub fn map<Map>(self, field: FieldName) -> MapBuilder<Self, Map>
+    where
+        List: Append<Map>,
+        Map: Default,
+    {
+        let base = self.struct_path.clone();
+        MapBuilder::new(self, base, field)
+    }
+
+    /// Start a [`VariantBuilder`] to record the parsing results for a nested
+    /// tagged union within the current struct.
+    pub fn variant<VariantValue>(self, field: FieldName) -> VariantBuilder<Self, VariantValue>
+    where
+        List: Append<VariantValue>,
+    {
+        let path = self.struct_path.join(PathSegment::Field(field));
+        VariantBuilder::new(self, path)
     }
 
     /// Run another validation step on the previously recorded `Ok` values if
@@ -95,10 +226,10 @@ where
     ///
     /// ```
     /// # use std::{convert::Infallible, num::NonZeroU16};
-    /// # use error_accumulator::{ErrorAccumulator, path::FieldName};
-    /// # const FOO: FieldName = FieldName::new_unchecked("foo");
-    /// # const BAR: FieldName = FieldName::new_unchecked("bar");
-    /// # const BAZ: FieldName = FieldName::new_unchecked("baz");
+    /// # use error_accumulator::{ErrorAccumulator, field, path::FieldName};
+    /// # const FOO: FieldName = field!("foo");
+    /// # const BAR: FieldName = field!("bar");
+    /// # const BAZ: FieldName = field!("baz");
     /// let res = ErrorAccumulator::new().strukt(FOO)
     ///     .field(BAR, NonZeroU16::try_from(16))
     ///     .field(BAZ, NonZeroU16::try_from(8))
@@ -130,7 +261,7 @@ where
             _marker,
         } = self;
 
-        let values = if errors.is_empty() {
+        let values = if !errors.has_errors() {
             let result = validator.validate(&values);
             append_or_record(values, &struct_path, result, &mut errors)
         } else {
@@ -146,6 +277,26 @@ where
         }
     }
 
+    /// Record a warning for the struct.
+    ///
+    /// Warnings are reported alongside errors but don't make the struct
+    /// invalid.
+    pub fn warn<W>(mut self, warning: W) -> Self
+    where
+        W: Error + Send + Sync + 'static,
+    {
+        self.errors
+            .push_with_severity(self.struct_path.clone(), Severity::Warning, warning);
+        self
+    }
+
+    /// Attach `code` to all errors recorded so far for the struct, including
+    /// nested ones, that don't have a code yet.
+    pub fn code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.errors.set_missing_codes(code.into());
+        self
+    }
+
     /// Provide a [`Constructor`] to build a struct values from all the recorded
     /// `Ok` values of the builder.
     pub fn on_ok<C>(self, constructor: C) -> BuilderFinisher<Parent, Value, List, C>
@@ -198,6 +349,14 @@ where
             _marker,
         }
     }
+
+    fn record_warnings(&mut self, warnings: AccumulatedError) {
+        self.errors.merge(warnings);
+    }
+
+    fn remaining_errors(&self) -> Option<usize> {
+        self.errors.remaining()
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +390,29 @@ mod tests {
         assert_eq!(res.as_str(), "42|dog")
     }
 
+    #[test]
+    fn should_record_optional_and_required_fields() {
+        let res = ErrorAccumulator::new()
+            .strukt(n("foo"))
+            .optional_field(n("bar"), None::<Result<u32, io::Error>>)
+            .required_field(n("baz"), None::<Result<u32, io::Error>>)
+            .on_ok(|bar, baz| (bar, baz))
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res.get_by_path(
+                &SourcePath::new()
+                    .join(PathSegment::Field(n("foo")))
+                    .join(PathSegment::Field(n("baz")))
+            )
+            .count(),
+            1
+        );
+    }
+
     #[test]
     fn should_record_nested_error() {
         let foo_struct = ErrorAccumulator::new().strukt(n("foo"));
@@ -0,0 +1,262 @@
+use std::error::Error;
+
+use crate::{
+    builder::{ErrorBuilderParent, StructBuilder, finish_child},
+    cons::Nil,
+    error::{AccumulatedError, Coded, MissingField, Severity, UnknownVariant},
+    path::{FieldName, PathSegment, SourcePath},
+};
+
+/// A builder to record the parsing results of a tagged union in the input.
+///
+/// The tag provided with [`tag()`](Self::tag), before any arm, selects exactly
+/// one of the [`arm()`](Self::arm)s to parse the input. If no arm matches the tag an
+/// [`UnknownVariant`] error listing all allowed tags is recorded.
+///
+/// ```
+/// # use std::convert::Infallible;
+/// # use error_accumulator::{ErrorAccumulator, field};
+/// #[derive(Debug, PartialEq)]
+/// enum Endpoint {
+///     Http { url: String },
+///     Tcp { port: u16 },
+/// }
+///
+/// let (endpoint,) = ErrorAccumulator::new()
+///     .variant(field!("endpoint"))
+///     .tag(field!("kind"), "tcp")
+///     .arm("http", |http| {
+///         http.field(field!("url"), Ok::<_, Infallible>("localhost".to_string()))
+///             .on_ok(|url| Endpoint::Http { url })
+///             .finish()
+///     })
+///     .arm("tcp", |tcp| {
+///         tcp.field(field!("port"), "8080".parse())
+///             .on_ok(|port| Endpoint::Tcp { port })
+///             .finish()
+///     })
+///     .finish()
+///     .analyse()
+///     .unwrap();
+///
+/// assert_eq!(endpoint, Endpoint::Tcp { port: 8080 });
+/// ```
+#[derive(Debug)]
+pub struct VariantBuilder<Parent, Value> {
+    parent: Parent,
+    path: SourcePath,
+    tag: Option<(SourcePath, String)>,
+    allowed: Vec<&'static str>,
+    result: Option<Result<Value, AccumulatedError>>,
+    errors: AccumulatedError,
+}
+
+impl<Parent, Value> VariantBuilder<Parent, Value>
+where
+    Parent: ErrorBuilderParent<Value>,
+{
+    pub(crate) fn new(parent: Parent, path: SourcePath) -> Self {
+        Self {
+            errors: AccumulatedError::with_limit(parent.remaining_errors()),
+            parent,
+            path,
+            tag: None,
+            allowed: Vec::new(),
+            result: None,
+        }
+    }
+
+    /// Provide the tag of the input that selects the arm to parse the input.
+    ///
+    /// `field` is the name of the tag's field within the tagged union. If no
+    /// tag is provided a [`MissingField`] error is recorded for the tagged
+    /// union.
+    ///
+    /// # Panics
+    ///
+    /// If an [`arm()`](Self::arm) was provided before as the arms are
+    /// selected when they are provided.
+    pub fn tag(mut self, field: FieldName, tag: impl Into<String>) -> Self {
+        assert!(
+            self.allowed.is_empty(),
+            "the tag must be provided before the arms of the tagged union"
+        );
+        self.tag = Some((self.path.join(PathSegment::Field(field)), tag.into()));
+        self
+    }
+
+    /// Provide the parser for one variant of the tagged union.
+    ///
+    /// The provided `Parser` is a closure that receives a [`StructBuilder`] for
+    /// the tagged union. It is only called if `tag` matches the tag of the
+    /// input and the [error limit](crate::ErrorAccumulator::max_errors) is not
+    /// reached yet.
+    pub fn arm<Parser>(mut self, tag: &'static str, parse: Parser) -> Self
+    where
+        Parser: FnOnce(StructBuilder<Self, Value, Nil>) -> Self,
+    {
+        self.allowed.push(tag);
+
+        let is_selected = self.result.is_none()
+            && !self.errors.is_exhausted()
+            && self
+                .tag
+                .as_ref()
+                .is_some_and(|(_, selected)| selected == tag);
+        if is_selected {
+            let path = self.path.clone();
+            parse(StructBuilder::new(self, path))
+        } else {
+            self
+        }
+    }
+
+    /// Record a warning for the tagged union.
+    ///
+    /// Warnings are reported alongside errors but don't make the tagged union
+    /// invalid.
+    pub fn warn<W>(mut self, warning: W) -> Self
+    where
+        W: Error + Send + Sync + 'static,
+    {
+        self.errors
+            .push_with_severity(self.path.clone(), Severity::Warning, warning);
+        self
+    }
+
+    /// Finish the `VariantBuilder` and pass the final result to the parent
+    /// builder.
+    pub fn finish(self) -> Parent::AfterRecord {
+        let Self {
+            parent,
+            path,
+            tag,
+            allowed,
+            result,
+            mut errors,
+        } = self;
+
+        match result {
+            Some(Ok(value)) => finish_child(parent, errors, || value),
+            Some(Err(child_errors)) => {
+                errors.merge(child_errors);
+                parent.finish_child_builder(Err(errors))
+            }
+            None => {
+                match tag {
+                    Some((tag_path, tag)) => {
+                        errors.push(tag_path, Coded::localized(UnknownVariant { tag, allowed }))
+                    }
+                    None => errors.push(path, Coded::localized(MissingField)),
+                }
+                parent.finish_child_builder(Err(errors))
+            }
+        }
+    }
+}
+
+impl<Parent, Value> ErrorBuilderParent<Value> for VariantBuilder<Parent, Value> {
+    type AfterRecord = Self;
+
+    fn finish_child_builder(
+        mut self,
+        child_result: Result<Value, AccumulatedError>,
+    ) -> Self::AfterRecord {
+        self.result = Some(child_result);
+        self
+    }
+
+    fn record_warnings(&mut self, warnings: AccumulatedError) {
+        self.errors.merge(warnings);
+    }
+
+    fn remaining_errors(&self) -> Option<usize> {
+        self.errors.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    use super::*;
+    use crate::{ErrorAccumulator, test_util::n};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Test {
+        Num(u32),
+        Text(String),
+    }
+
+    fn parse(tag: &str, value: &str) -> Result<(Test,), AccumulatedError> {
+        ErrorAccumulator::new()
+            .variant(n("foo"))
+            .tag(n("kind"), tag)
+            .arm("num", |rec| {
+                rec.field(n("num"), value.parse()).on_ok(Test::Num).finish()
+            })
+            .arm("text", |rec| {
+                rec.field(n("text"), Ok::<_, ParseIntError>(value.to_string()))
+                    .on_ok(Test::Text)
+                    .finish()
+            })
+            .finish()
+            .analyse()
+    }
+
+    #[test]
+    fn should_parse_selected_variant() {
+        assert_eq!(parse("num", "42").unwrap(), (Test::Num(42),));
+        assert_eq!(
+            parse("text", "42").unwrap(),
+            (Test::Text("42".to_string()),)
+        );
+    }
+
+    #[test]
+    fn should_record_error_of_selected_variant() {
+        let err = parse("num", "x").unwrap_err();
+
+        assert_eq!(err.len(), 1);
+        assert_eq!(err.get_by_path(&"foo.num".parse().unwrap()).count(), 1);
+    }
+
+    #[test]
+    fn should_record_unknown_variant() {
+        let err = parse("bool", "true").unwrap_err();
+
+        let (path, error) = err.get_by_type::<UnknownVariant>().next().unwrap();
+        assert_eq!(path, &"foo.kind".parse().unwrap());
+        assert_eq!(error.tag, "bool");
+        assert_eq!(error.allowed, vec!["num", "text"]);
+    }
+
+    #[test]
+    #[should_panic = "the tag must be provided before the arms"]
+    fn should_reject_tag_after_arms() {
+        let _ = ErrorAccumulator::new()
+            .variant(n("foo"))
+            .arm("num", |rec| {
+                rec.field(n("num"), "1".parse()).on_ok(Test::Num).finish()
+            })
+            .tag(n("kind"), "num");
+    }
+
+    #[test]
+    fn should_record_missing_tag() {
+        let err = ErrorAccumulator::new()
+            .strukt(n("bar"))
+            .variant(n("foo"))
+            .arm("num", |rec| {
+                rec.field(n("num"), "1".parse()).on_ok(Test::Num).finish()
+            })
+            .finish()
+            .on_ok(|foo| foo)
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(err.get_by_path(&"bar.foo".parse().unwrap()).count(), 1);
+        assert_eq!(err.get_by_type::<MissingField>().count(), 1);
+    }
+}
@@ -9,8 +9,8 @@ use crate::cons::{AsRefTuple, Cons, Nil};
 /// This is used for `on_ok()` methods so the provided closures can take each
 /// recorded value as separate argument instead of deconstructing tuples.
 ///
-/// There are default implementations for [`FnMut`] closures with up to 10
-/// arguments.
+/// There are default implementations for [`FnMut`] closures with up to 12
+/// arguments or up to 64 arguments if the `large-tuples` feature is enabled.
 pub trait Constructor<In, Out> {
     /// Take the input and convert it into output.
     fn construct(self, input: In) -> Out;
@@ -19,8 +19,9 @@ pub trait Constructor<In, Out> {
 /// Marker trait for types that can validate a list of values into something
 /// potentially fallible.
 ///
-/// There are default implementations for [`FnMut`] closures with up to 10
-/// reference arguments.
+/// There are default implementations for [`FnMut`] closures with up to 12
+/// reference arguments or up to 64 reference arguments if the `large-tuples`
+/// feature is enabled.
 pub trait ListValidator<List, Out, Err> {
     /// Transform an input. May fails doing so.
     fn validate(self, values: &List) -> Result<Out, Err>;
@@ -70,6 +71,9 @@ impl_constructor!(A, B, C, D, E, F, G, H, I, J);
 impl_constructor!(A, B, C, D, E, F, G, H, I, J, K);
 impl_constructor!(A, B, C, D, E, F, G, H, I, J, K, L);
 
+#[cfg(feature = "large-tuples")]
+crate::cons::large_arities!(impl_constructor);
+
 impl<Out, Func> ListValidator<Nil, Out, Infallible> for Func
 where
     Func: FnMut() -> Result<Out, Infallible>,
@@ -129,3 +133,6 @@ impl_validator!(A, B, C, D, E, F, G, H, I);
 impl_validator!(A, B, C, D, E, F, G, H, I, J);
 impl_validator!(A, B, C, D, E, F, G, H, I, J, K);
 impl_validator!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(feature = "large-tuples")]
+crate::cons::large_arities!(impl_validator);
@@ -0,0 +1,762 @@
+//! Deserialize with [`serde`] while accumulating errors.
+//!
+//! Usually deserialization stops at the first error. With
+//! [`from_deserializer()`] the [`SourcePath`] of each error is tracked and
+//! fields that use [`or_default()`] record their errors instead of aborting
+//! the deserialization:
+//!
+//! ```
+//! use error_accumulator::de;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Host {
+//!     #[serde(deserialize_with = "de::or_default")]
+//!     port: u16,
+//! }
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Config {
+//!     #[serde(deserialize_with = "de::or_default")]
+//!     retries: u8,
+//!     hosts: Vec<Host>,
+//! }
+//!
+//! let input = r#"{ "retries": -1, "hosts": [{ "port": 80 }, { "port": "http" }] }"#;
+//! let mut deserializer = serde_json::Deserializer::from_str(input);
+//! let errors = de::from_deserializer::<Config, _>(&mut deserializer).unwrap_err();
+//!
+//! assert_eq!(errors.len(), 2);
+//! assert_eq!(errors.get_by_path(&"retries".parse().unwrap()).count(), 1);
+//! assert_eq!(errors.get_by_path(&"hosts[1].port".parse().unwrap()).count(), 1);
+//! ```
+//!
+//! Because serde's visitors can not be resumed after an error, errors of
+//! fields without [`or_default()`] still end the deserialization. They are
+//! recorded with their path as well.
+
+use std::{cell::RefCell, fmt};
+
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, Visitor};
+
+use crate::{
+    error::AccumulatedError,
+    path::{FieldName, PathSegment, SourcePath},
+};
+
+thread_local! {
+    static CONTEXT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+/// An error that occured while deserializing.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct Error {
+    message: String,
+}
+
+/// Deserialize `T` from `deserializer` and return all accumulated errors on
+/// failure.
+///
+/// See the [module documentation](self) for more details.
+pub fn from_deserializer<'de, T, D>(deserializer: D) -> Result<T, AccumulatedError>
+where
+    T: de::Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let guard = ContextGuard::enter();
+    let result = T::deserialize(Tracked::new(deserializer));
+    let Context {
+        mut errors,
+        error_path,
+        ..
+    } = guard.exit();
+
+    match result {
+        Ok(value) if errors.is_empty() => Ok(value),
+        Ok(_) => Err(errors),
+        Err(error) => {
+            errors.push(error_path.unwrap_or_default(), Error::new(error));
+            Err(errors)
+        }
+    }
+}
+
+/// Deserialize a value and record the error in case of failure.
+///
+/// Use with `#[serde(deserialize_with = "error_accumulator::de::or_default")]`
+/// on a field so deserialization continues after an error of the field. The
+/// recorded error is returned by [`from_deserializer()`] and the field is set
+/// to its default value in the meantime.
+///
+/// Combine with `#[serde(default)]` if the field may be missing in the input.
+///
+/// When not used within [`from_deserializer()`] the error is returned as
+/// usual.
+pub fn or_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: de::Deserialize<'de> + Default,
+{
+    T::deserialize(deserializer).or_else(|error| {
+        let path = CONTEXT.with_borrow_mut(|context| {
+            context.as_mut().map(|context| {
+                context
+                    .error_path
+                    .take()
+                    .unwrap_or_else(|| context.current_path())
+            })
+        });
+
+        match path {
+            Some(path) => {
+                CONTEXT.with_borrow_mut(|context| {
+                    if let Some(context) = context {
+                        context.errors.push(path, Error::new(error));
+                    }
+                });
+                Ok(T::default())
+            }
+            None => Err(error),
+        }
+    })
+}
+
+impl Error {
+    fn new(error: impl fmt::Display) -> Self {
+        Self {
+            message: error.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Context {
+    errors: AccumulatedError,
+    tokens: Vec<Token>,
+    /// The path of the deepest error that was not yet recorded.
+    error_path: Option<SourcePath>,
+}
+
+#[derive(Debug)]
+enum Token {
+    Field(String),
+    Key(String),
+    Index(usize),
+}
+
+impl Context {
+    fn current_path(&self) -> SourcePath {
+        let mut path = SourcePath::new();
+        let mut pending: Option<FieldName> = None;
+        for token in &self.tokens {
+            match token {
+                Token::Field(name) => {
+                    if let Some(name) = pending.take() {
+                        path = path.join(PathSegment::Field(name));
+                    }
+                    pending = Some(FieldName::new(name.clone()));
+                }
+                Token::Index(index) => {
+                    path = path.join(match pending.take() {
+                        Some(name) => PathSegment::Array {
+                            name,
+                            index: *index,
+                        },
+                        None => PathSegment::Index(*index),
+                    });
+                }
+                Token::Key(key) => match pending.take() {
+                    Some(name) => {
+                        path = path.join(PathSegment::Key {
+                            name,
+                            key: key.clone(),
+                        });
+                    }
+                    None => pending = Some(FieldName::new(key.clone())),
+                },
+            }
+        }
+        if let Some(name) = pending {
+            path = path.join(PathSegment::Field(name));
+        }
+        path
+    }
+}
+
+/// Installs a fresh [`Context`] and restores the previous one on drop.
+struct ContextGuard {
+    previous: Option<Option<Context>>,
+}
+
+impl ContextGuard {
+    fn enter() -> Self {
+        let previous = CONTEXT.replace(Some(Context::default()));
+        Self {
+            previous: Some(previous),
+        }
+    }
+
+    fn exit(mut self) -> Context {
+        let previous = self.previous.take().expect("only taken once");
+        CONTEXT
+            .replace(previous)
+            .expect("context was installed on enter")
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            CONTEXT.set(previous);
+        }
+    }
+}
+
+fn push_token(token: Token) {
+    CONTEXT.with_borrow_mut(|context| {
+        if let Some(context) = context {
+            context.tokens.push(token);
+        }
+    });
+}
+
+fn pop_token() {
+    CONTEXT.with_borrow_mut(|context| {
+        if let Some(context) = context {
+            context.tokens.pop();
+        }
+    });
+}
+
+fn mark_error() {
+    CONTEXT.with_borrow_mut(|context| {
+        if let Some(context) = context
+            && context.error_path.is_none()
+        {
+            context.error_path = Some(context.current_path());
+        }
+    });
+}
+
+/// Whether the entries of a map are the fields of a struct.
+#[derive(Debug, Clone, Copy)]
+enum MapKind {
+    Struct,
+    Map,
+}
+
+/// Wraps a [`Deserializer`] to track the path of nested values.
+struct Tracked<'k, D> {
+    inner: D,
+    /// Set if the deserialized value is a map key.
+    key: Option<&'k mut Option<String>>,
+}
+
+impl<D> Tracked<'_, D> {
+    fn new(inner: D) -> Self {
+        Self { inner, key: None }
+    }
+}
+
+impl<'k, D> Tracked<'k, D> {
+    fn visitor<V>(self, visitor: V, kind: MapKind) -> (D, TrackedVisitor<'k, V>) {
+        (
+            self.inner,
+            TrackedVisitor {
+                inner: visitor,
+                kind,
+                key: self.key,
+            },
+        )
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident ( $($arg:ident : $ty:ty),* ) => $kind:ident,)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let kind = MapKind::$kind;
+                let (inner, visitor) = self.visitor(visitor, kind);
+                inner.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D> Deserializer<'de> for Tracked<'_, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any() => Map,
+        deserialize_bool() => Map,
+        deserialize_i8() => Map,
+        deserialize_i16() => Map,
+        deserialize_i32() => Map,
+        deserialize_i64() => Map,
+        deserialize_i128() => Map,
+        deserialize_u8() => Map,
+        deserialize_u16() => Map,
+        deserialize_u32() => Map,
+        deserialize_u64() => Map,
+        deserialize_u128() => Map,
+        deserialize_f32() => Map,
+        deserialize_f64() => Map,
+        deserialize_char() => Map,
+        deserialize_str() => Map,
+        deserialize_string() => Map,
+        deserialize_bytes() => Map,
+        deserialize_byte_buf() => Map,
+        deserialize_option() => Map,
+        deserialize_unit() => Map,
+        deserialize_unit_struct(name: &'static str) => Map,
+        deserialize_newtype_struct(name: &'static str) => Map,
+        deserialize_seq() => Map,
+        deserialize_tuple(len: usize) => Map,
+        deserialize_tuple_struct(name: &'static str, len: usize) => Map,
+        deserialize_map() => Map,
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]) => Struct,
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]) => Map,
+        deserialize_identifier() => Map,
+        deserialize_ignored_any() => Map,
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Wraps a [`Visitor`] to track the path of nested values.
+struct TrackedVisitor<'k, V> {
+    inner: V,
+    kind: MapKind,
+    key: Option<&'k mut Option<String>>,
+}
+
+impl<V> TrackedVisitor<'_, V> {
+    fn capture(&mut self, key: impl fmt::Display) {
+        if let Some(slot) = &mut self.key {
+            **slot = Some(key.to_string());
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident ( $ty:ty ),)*) => {
+        $(
+            fn $method<E>(mut self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.capture(&v);
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V> Visitor<'de> for TrackedVisitor<'_, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_byte_buf(v)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner.visit_some(Tracked::new(deserializer))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner.visit_newtype_struct(Tracked::new(deserializer))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.inner.visit_seq(TrackedSeq {
+            inner: seq,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.inner.visit_map(TrackedMap {
+            inner: map,
+            kind: self.kind,
+            key: None,
+        })
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        self.inner.visit_enum(TrackedEnum { inner: data })
+    }
+}
+
+/// Wraps a [`DeserializeSeed`] of a nested value to track its path.
+struct TrackedSeed<S> {
+    inner: S,
+}
+
+impl<'de, S> DeserializeSeed<'de> for TrackedSeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner
+            .deserialize(Tracked::new(deserializer))
+            .inspect_err(|_| mark_error())
+    }
+}
+
+/// Wraps the [`DeserializeSeed`] of a map key to capture the key.
+struct KeySeed<'k, S> {
+    inner: S,
+    key: &'k mut Option<String>,
+}
+
+impl<'de, S> DeserializeSeed<'de> for KeySeed<'_, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner.deserialize(Tracked {
+            inner: deserializer,
+            key: Some(self.key),
+        })
+    }
+}
+
+struct TrackedSeq<A> {
+    inner: A,
+    index: usize,
+}
+
+impl<'de, A> SeqAccess<'de> for TrackedSeq<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        push_token(Token::Index(self.index));
+        let result = self.inner.next_element_seed(TrackedSeed { inner: seed });
+        pop_token();
+        self.index += 1;
+        result
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct TrackedMap<A> {
+    inner: A,
+    kind: MapKind,
+    key: Option<String>,
+}
+
+impl<'de, A> MapAccess<'de> for TrackedMap<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.key = None;
+        self.inner.next_key_seed(KeySeed {
+            inner: seed,
+            key: &mut self.key,
+        })
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let token = self.key.take().map(|key| match self.kind {
+            MapKind::Struct => Token::Field(key),
+            MapKind::Map => Token::Key(key),
+        });
+        let pushed = token.is_some();
+        if let Some(token) = token {
+            push_token(token);
+        }
+        let result = self.inner.next_value_seed(TrackedSeed { inner: seed });
+        if pushed {
+            pop_token();
+        }
+        result
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct TrackedEnum<A> {
+    inner: A,
+}
+
+impl<'de, A> EnumAccess<'de> for TrackedEnum<A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = TrackedVariant<A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.inner
+            .variant_seed(seed)
+            .map(|(value, variant)| (value, TrackedVariant { inner: variant }))
+    }
+}
+
+struct TrackedVariant<A> {
+    inner: A,
+}
+
+impl<'de, A> de::VariantAccess<'de> for TrackedVariant<A>
+where
+    A: de::VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.inner.newtype_variant_seed(TrackedSeed { inner: seed })
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.tuple_variant(
+            len,
+            TrackedVisitor {
+                inner: visitor,
+                kind: MapKind::Map,
+                key: None,
+            },
+        )
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.struct_variant(
+            fields,
+            TrackedVisitor {
+                inner: visitor,
+                kind: MapKind::Struct,
+                key: None,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Host {
+        #[serde(deserialize_with = "or_default")]
+        port: u16,
+        #[serde(default, deserialize_with = "or_default")]
+        weight: u8,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        #[serde(deserialize_with = "or_default")]
+        name: String,
+        hosts: Vec<Host>,
+        #[serde(default)]
+        labels: BTreeMap<String, Host>,
+    }
+
+    fn parse(input: &str) -> Result<Config, AccumulatedError> {
+        let mut deserializer = serde_json::Deserializer::from_str(input);
+        from_deserializer(&mut deserializer)
+    }
+
+    #[test]
+    fn should_deserialize_valid_input() {
+        let config = parse(
+            r#"{ "name": "test", "hosts": [{ "port": 80 }], "labels": { "a": { "port": 1 } } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.name, "test");
+        assert_eq!(config.hosts[0].port, 80);
+        assert_eq!(config.hosts[0].weight, 0);
+        assert_eq!(config.labels["a"].port, 1);
+    }
+
+    #[test]
+    fn should_accumulate_recoverable_errors() {
+        let errors = parse(
+            r#"{
+                "name": 42,
+                "hosts": [{ "port": 80 }, { "port": -1, "weight": 1000 }],
+                "labels": { "a": { "port": "x" } }
+            }"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(errors.len(), 4);
+        for path in [
+            "name",
+            "hosts[1].port",
+            "hosts[1].weight",
+            "labels[\"a\"].port",
+        ] {
+            assert_eq!(
+                errors.get_by_path(&path.parse().unwrap()).count(),
+                1,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_record_path_of_fatal_error() {
+        let errors =
+            parse(r#"{ "name": 42, "hosts": [{ "port": 80 }, { "weight": 1 }] }"#).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.get_by_path(&"hosts[1]".parse().unwrap()).count(), 1);
+    }
+
+    #[test]
+    fn should_record_index_of_top_level_array() {
+        let mut deserializer =
+            serde_json::Deserializer::from_str(r#"[[{ "port": 1 }, { "port": -1 }]]"#);
+        let errors = from_deserializer::<Vec<Vec<Host>>, _>(&mut deserializer).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors.get_by_path(&"[0][1].port".parse().unwrap()).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn should_return_error_outside_of_context() {
+        let result = serde_json::from_str::<Host>(r#"{ "port": -1 }"#);
+
+        assert!(result.is_err());
+    }
+}
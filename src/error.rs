@@ -1,16 +1,220 @@
 //! Provide [`AccumulatedError`] to present a collection of errors.
 
-use std::{error::Error, fmt};
+use std::{
+    any,
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt,
+    iter::FusedIterator,
+};
 
-use crate::path::SourcePath;
+use crate::{path::SourcePath, source::SourceMap};
+
+mod i18n;
+mod problem_details;
+mod tree;
+
+pub use self::{
+    i18n::{DisplayLocalized, Localize, Localized, MessageArg, MessageArgs, MessageCatalog},
+    problem_details::{InvalidParam, ProblemDetails},
+    tree::{ColorChoice, DisplayTree},
+};
 
 /// A list of recorded errors and their source's path in the input.
+///
+/// Each error has a [`Severity`]. Only errors with [`Severity::Error`] are
+/// fatal, warnings and infos are reported alongside but don't fail
+/// [`analyse()`](crate::ErrorAccumulator::analyse).
+///
+/// Errors may have an [error code](ErrorCode) to look them up in logs or
+/// documentation.
+///
+/// The alternate format `{:#}` of [`Display`](fmt::Display) and the other
+/// renderers lists the [source chain](Chain) of each error as `caused by: ...`
+/// lines. [`Error::source()`] is the first error with [`Severity::Error`].
+///
+/// With the `serde` feature enabled it serializes as a list of objects like
+/// `{ "path": "hosts[1].url", "segments": [...], "severity": "error", "code": "...", "message": "...", "type": "..." }`
+/// where `type` is the [type name](std::any::type_name) of the recorded error.
+/// `type` is `null` for errors that were added as boxed trait objects, e.g. via
+/// [`Extend`] with `(SourcePath, Box<dyn Error>)` tuples.
 #[derive(Debug, Default)]
 pub struct AccumulatedError {
-    errors: Vec<(SourcePath, Box<dyn Error + Send + Sync + 'static>)>,
+    errors: Vec<Entry>,
+    /// Maximum number of errors with [`Severity::Error`] to store.
+    limit: Option<usize>,
+    suppressed: usize,
+    /// Whether input was skipped because the limit was reached.
+    truncated: bool,
+}
+
+/// Displays an [`AccumulatedError`] with its paths as JSON Pointers.
+///
+/// See [`AccumulatedError::display_json_pointers()`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayJsonPointers<'a>(&'a AccumulatedError);
+
+/// Displays an [`AccumulatedError`] with the locations of the errors in the
+/// original document.
+///
+/// See [`AccumulatedError::display_with_source()`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayWithSource<'a> {
+    errors: &'a AccumulatedError,
+    source: &'a SourceMap,
+}
+
+/// An [`Iterator`] over an error and its [sources](Error::source).
+///
+/// See [`AccumulatedError::iter_chains()`].
+#[derive(Debug, Clone)]
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Error + 'static)>,
+}
+
+/// An owning [`Iterator`] over the errors of an [`AccumulatedError`].
+#[derive(Debug)]
+pub struct IntoIter {
+    inner: std::vec::IntoIter<Entry>,
+}
+
+type BoxError = Box<dyn Error + Send + Sync + 'static>;
+
+/// An error of an [`AccumulatedError`] with its path, severity, code and
+/// message key.
+///
+/// Entries are yielded by the owning iterators, e.g.
+/// [`into_iter()`](AccumulatedError::into_iter) and
+/// [`drain_by_path()`](AccumulatedError::drain_by_path), so that collecting
+/// them into another `AccumulatedError` keeps all of it.
+#[derive(Debug)]
+pub struct Entry {
+    path: SourcePath,
+    severity: Severity,
+    code: Option<Cow<'static, str>>,
+    message: Option<i18n::Message>,
+    error: BoxError,
+    #[cfg_attr(not(feature = "serde"), expect(dead_code))]
+    type_name: Option<&'static str>,
+}
+
+/// Error types that have a stable code, e.g. `E0042` or `config.url.invalid`.
+///
+/// The code is attached when the error is recorded with one of the methods
+/// for coded errors, e.g.
+/// [`ErrorAccumulator::coded_field()`](crate::ErrorAccumulator::coded_field),
+/// [`FieldBuilder::coded_value()`](crate::builder::FieldBuilder::coded_value)
+/// or [`FieldBuilder::check_coded()`](crate::builder::FieldBuilder::check_coded),
+/// or when it is wrapped in [`Coded::from_error()`]. Errors recorded with the
+/// other methods, e.g. [`field()`](crate::ErrorAccumulator::field), have no
+/// code as these accept any error.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use error_accumulator::{ErrorAccumulator, error::ErrorCode, field};
+/// #[derive(Debug, thiserror::Error)]
+/// #[error("invalid URL")]
+/// struct InvalidUrl;
+///
+/// impl ErrorCode for InvalidUrl {
+///     fn code(&self) -> Cow<'static, str> {
+///         "config.url.invalid".into()
+///     }
+/// }
+///
+/// let errors = ErrorAccumulator::new()
+///     .coded_field(field!("url"), Err::<String, _>(InvalidUrl))
+///     .analyse()
+///     .unwrap_err();
+///
+/// assert_eq!(errors.get_by_code("config.url.invalid").count(), 1);
+/// ```
+pub trait ErrorCode: Error {
+    /// The code of the error.
+    fn code(&self) -> Cow<'static, str>;
+}
+
+/// An error with a code attached.
+///
+/// Recording a `Coded` error stores the wrapped error with its code in the
+/// [`AccumulatedError`], i.e. the wrapper itself is not stored.
+#[derive(Debug)]
+pub struct Coded {
+    code: Cow<'static, str>,
+    error: BoxError,
+    type_name: &'static str,
+}
+
+/// How severe a recorded error is.
+///
+/// Severities are ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// Information about the input, e.g. a default value that was used.
+    Info,
+    /// Suspicious but valid input, e.g. a deprecated field.
+    Warning,
+    /// Invalid input.
+    Error,
+}
+
+/// The error recorded for a required field that is missing in the input.
+///
+/// Its code and message key are `missing_field`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("required field is missing")]
+pub struct MissingField;
+
+/// The error recorded for a tagged union with a tag that does not match any of
+/// the expected variants.
+///
+/// Its code and message key are `unknown_variant` with the arguments `tag` and
+/// `allowed`, a comma-separated list.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown variant '{tag}', expected one of {allowed:?}")]
+pub struct UnknownVariant {
+    /// The tag found in the input.
+    pub tag: String,
+    /// All tags that are allowed.
+    pub allowed: Vec<&'static str>,
 }
 
 impl AccumulatedError {
+    /// Iterate over all accumulated errors and their paths.
+    ///
+    /// Errors are in accumulation order.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&SourcePath, &(dyn Error + Send + Sync + 'static))> {
+        self.errors
+            .iter()
+            .map(|entry| (&entry.path, entry.error.as_ref()))
+    }
+
+    /// Iterate mutably over all accumulated errors and their paths.
+    ///
+    /// Errors are in accumulation order.
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&mut SourcePath, &mut (dyn Error + Send + Sync + 'static))> {
+        self.errors
+            .iter_mut()
+            .map(|entry| (&mut entry.path, entry.error.as_mut()))
+    }
+
+    /// Iterate over the [source chains](Chain) of all accumulated errors and
+    /// their paths.
+    ///
+    /// Errors are in accumulation order.
+    pub fn iter_chains(&self) -> impl Iterator<Item = (&SourcePath, Chain<'_>)> {
+        self.errors
+            .iter()
+            .map(|entry| (&entry.path, Chain::new(entry.error.as_ref())))
+    }
+
     /// Get all accumulated errors of the given type.
     ///
     /// Errors are in accumulation order.
@@ -20,7 +224,112 @@ impl AccumulatedError {
     {
         self.errors
             .iter()
-            .filter_map(|(path, stored)| stored.downcast_ref().map(|typed| (path, typed)))
+            .filter_map(|entry| entry.error.downcast_ref().map(|typed| (&entry.path, typed)))
+    }
+
+    /// Get all accumulated errors of the given severity.
+    ///
+    /// Errors are in accumulation order.
+    pub fn get_by_severity(
+        &self,
+        severity: Severity,
+    ) -> impl Iterator<Item = (&SourcePath, &(dyn Error + Send + Sync + 'static))> {
+        self.errors
+            .iter()
+            .filter(move |entry| entry.severity == severity)
+            .map(|entry| (&entry.path, entry.error.as_ref()))
+    }
+
+    /// The highest severity of all accumulated errors.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.errors.iter().map(|entry| entry.severity).max()
+    }
+
+    /// Get all accumulated errors with the given code.
+    ///
+    /// Errors are in accumulation order.
+    pub fn get_by_code(
+        &self,
+        code: &str,
+    ) -> impl Iterator<Item = (&SourcePath, &(dyn Error + Send + Sync + 'static))> {
+        self.errors
+            .iter()
+            .filter(move |entry| entry.code.as_deref() == Some(code))
+            .map(|entry| (&entry.path, entry.error.as_ref()))
+    }
+
+    /// True if at least one error with [`Severity::Error`] is stored or was
+    /// suppressed.
+    pub fn has_errors(&self) -> bool {
+        self.suppressed > 0
+            || self.truncated
+            || self
+                .errors
+                .iter()
+                .any(|entry| entry.severity == Severity::Error)
+    }
+
+    /// Number of errors that were not stored because the limit set with
+    /// [`ErrorAccumulator::max_errors()`](crate::ErrorAccumulator::max_errors)
+    /// was reached.
+    ///
+    /// This is a lower bound as the builders stop parsing once the limit is
+    /// reached, i.e. errors of the skipped input are not counted.
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+
+    /// True if the builders skipped input because the limit set with
+    /// [`ErrorAccumulator::max_errors()`](crate::ErrorAccumulator::max_errors)
+    /// was reached, i.e. there may be further errors even if
+    /// [`suppressed()`](Self::suppressed) is zero.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Record that input was skipped because the limit was reached.
+    pub(crate) fn set_truncated(&mut self) {
+        self.truncated = true;
+    }
+
+    /// An empty `AccumulatedError` that stores at most `limit` errors with
+    /// [`Severity::Error`].
+    pub(crate) fn with_limit(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// Set the maximum number of errors with [`Severity::Error`] to store.
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = Some(limit);
+    }
+
+    /// How many more errors with [`Severity::Error`] are stored before further
+    /// errors are suppressed, `None` if unlimited.
+    pub(crate) fn remaining(&self) -> Option<usize> {
+        self.limit.map(|limit| {
+            let stored = self
+                .errors
+                .iter()
+                .filter(|entry| entry.severity == Severity::Error)
+                .count();
+            limit.saturating_sub(stored)
+        })
+    }
+
+    /// True if further errors with [`Severity::Error`] are suppressed.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+
+    fn push_entry(&mut self, entry: Entry) {
+        if entry.severity == Severity::Error && self.is_exhausted() {
+            self.suppressed += 1;
+        } else {
+            self.errors.push(entry);
+        }
     }
 
     /// Get all accumulated errors for a given path.
@@ -32,7 +341,101 @@ impl AccumulatedError {
     ) -> impl Iterator<Item = &Box<dyn Error + Send + Sync>> {
         self.errors
             .iter()
-            .filter_map(move |(error_path, stored)| (error_path == path).then_some(stored))
+            .filter_map(move |entry| (&entry.path == path).then_some(&entry.error))
+    }
+
+    /// Get all accumulated errors for a given path and all paths below it.
+    ///
+    /// For example, errors for `hosts[2]` and `hosts[2].url` are both under
+    /// `hosts[2]`. Errors are in accumulation order.
+    pub fn get_under(
+        &self,
+        base: &SourcePath,
+    ) -> impl Iterator<Item = (&SourcePath, &(dyn Error + Send + Sync + 'static))> {
+        self.iter().filter(move |(path, _)| path.starts_with(base))
+    }
+
+    /// Split off all errors under `base`, see [`get_under()`](Self::get_under).
+    ///
+    /// The paths of the returned errors are relative to `base`, e.g. an error
+    /// for `hosts[2].url` has the path `url` in the subtree of `hosts[2]`.
+    pub fn subtree(self, base: &SourcePath) -> Self {
+        let errors = self
+            .errors
+            .into_iter()
+            .filter_map(|entry| {
+                entry
+                    .path
+                    .strip_prefix(base)
+                    .map(|path| Entry { path, ..entry })
+            })
+            .collect();
+        Self {
+            errors,
+            ..Self::default()
+        }
+    }
+
+    /// All paths with at least one error.
+    pub fn paths(&self) -> BTreeSet<&SourcePath> {
+        self.errors.iter().map(|entry| &entry.path).collect()
+    }
+
+    /// Group all accumulated errors by their path.
+    ///
+    /// Errors of each path are in accumulation order.
+    pub fn group_by_path(&self) -> BTreeMap<SourcePath, Vec<&(dyn Error + Send + Sync + 'static)>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for (path, error) in self.iter() {
+            groups.entry(path.clone()).or_default().push(error);
+        }
+        groups
+    }
+
+    /// Display the errors like [`Display`](fmt::Display) but with their paths
+    /// as [JSON Pointers](SourcePath::to_json_pointer), e.g. `/hosts/1/url`
+    /// instead of `hosts[1].url`.
+    pub fn display_json_pointers(&self) -> DisplayJsonPointers<'_> {
+        DisplayJsonPointers(self)
+    }
+
+    /// Display the errors like [`Display`](fmt::Display) but prefixed with
+    /// their location in the original document, e.g.
+    /// `config.yaml:3:10: hosts[0].url: ...`.
+    ///
+    /// Errors that can't be located are prefixed with the document's name
+    /// only.
+    pub fn display_with_source<'a>(&'a self, source: &'a SourceMap) -> DisplayWithSource<'a> {
+        DisplayWithSource {
+            errors: self,
+            source,
+        }
+    }
+
+    /// Display the errors as an indented tree that groups them by the
+    /// segments of their paths, e.g. `hosts` → `[3]` → `url: ...`.
+    ///
+    /// See [`DisplayTree`] for the options.
+    pub fn display_tree(&self) -> DisplayTree<'_> {
+        DisplayTree::new(self)
+    }
+
+    /// Display the errors like [`Display`](fmt::Display) but with their
+    /// messages translated by `catalog`.
+    ///
+    /// See [`MessageCatalog`] for how messages are looked up.
+    pub fn display_localized<'a>(
+        &'a self,
+        catalog: &'a dyn MessageCatalog,
+    ) -> DisplayLocalized<'a> {
+        DisplayLocalized::new(self, catalog)
+    }
+
+    /// Convert the errors into a [`miette::Diagnostic`] labeling each error in
+    /// the original document.
+    #[cfg(feature = "miette")]
+    pub fn diagnostic<'a>(&'a self, source: &'a SourceMap) -> crate::source::Diagnostic<'a> {
+        crate::source::Diagnostic::new(self, source)
     }
 
     /// Number of stored errors.
@@ -45,29 +448,465 @@ impl AccumulatedError {
         self.errors.is_empty()
     }
 
-    pub(crate) fn append<E>(&mut self, path: SourcePath, error: E)
+    /// Add an error for the given path.
+    pub fn push<E>(&mut self, path: SourcePath, error: E)
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.push_with_severity(path, Severity::Error, error);
+    }
+
+    /// Add an error with the given severity for the given path.
+    ///
+    /// If `error` is [`Coded`] its code is stored as well.
+    pub fn push_with_severity<E>(&mut self, path: SourcePath, severity: Severity, error: E)
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.push_entry(Entry::new(
+            path,
+            severity,
+            Box::new(error),
+            Some(any::type_name::<E>()),
+        ));
+    }
+
+    /// Add an error with the given code for the given path.
+    pub fn push_with_code<E>(
+        &mut self,
+        path: SourcePath,
+        code: impl Into<Cow<'static, str>>,
+        error: E,
+    ) where
+        E: Error + Send + Sync + 'static,
+    {
+        self.push(path, Coded::new(code, error));
+    }
+
+    /// Attach `code` to all errors that don't have a code yet.
+    pub(crate) fn set_missing_codes(&mut self, code: Cow<'static, str>) {
+        for entry in &mut self.errors {
+            entry.code.get_or_insert_with(|| code.clone());
+        }
+    }
+
+    /// Move all errors of `other` into `self`.
+    pub fn merge(&mut self, other: AccumulatedError) {
+        self.suppressed += other.suppressed;
+        self.truncated |= other.truncated;
+        for entry in other.errors {
+            self.push_entry(entry);
+        }
+    }
+
+    /// Keep only the errors for which `keep` returns `true`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&SourcePath, &(dyn Error + Send + Sync + 'static)) -> bool,
+    {
+        self.errors
+            .retain(|entry| keep(&entry.path, entry.error.as_ref()));
+    }
+
+    /// Remove all errors for a given path and return them.
+    ///
+    /// Errors are in accumulation order. Only the errors that are yielded by
+    /// the returned [`Iterator`] are removed.
+    pub fn drain_by_path(&mut self, path: &SourcePath) -> impl Iterator<Item = Entry> {
+        self.errors.extract_if(.., move |entry| &entry.path == path)
+    }
+
+    /// Split off all errors of the given type.
+    ///
+    /// Returns the errors of type `E` and the remaining errors, both in
+    /// accumulation order.
+    pub fn partition_by_type<E>(self) -> (Vec<(SourcePath, E)>, Self)
     where
         E: Error + Send + Sync + 'static,
     {
-        self.errors.push((path, Box::new(error)));
+        let Self {
+            errors,
+            limit,
+            suppressed,
+            truncated,
+        } = self;
+        let mut typed = Vec::new();
+        let mut rest = Vec::new();
+        for entry in errors {
+            if entry.error.is::<E>() {
+                let error = entry.error.downcast().expect("type was checked");
+                typed.push((entry.path, *error));
+            } else {
+                rest.push(entry);
+            }
+        }
+        let rest = Self {
+            errors: rest,
+            limit,
+            suppressed,
+            truncated,
+        };
+        (typed, rest)
+    }
+}
+
+impl IntoIterator for AccumulatedError {
+    type Item = Entry;
+    type IntoIter = IntoIter;
+
+    /// Consume the errors in accumulation order.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.errors.into_iter(),
+        }
+    }
+}
+
+impl Iterator for IntoIter {
+    type Item = Entry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl Extend<Entry> for AccumulatedError {
+    fn extend<T: IntoIterator<Item = Entry>>(&mut self, iter: T) {
+        for entry in iter {
+            self.push_entry(entry);
+        }
     }
+}
 
-    pub(crate) fn merge(&mut self, other: AccumulatedError) {
-        self.errors.extend(other.errors);
+impl FromIterator<Entry> for AccumulatedError {
+    fn from_iter<T: IntoIterator<Item = Entry>>(iter: T) -> Self {
+        let mut errors = Self::default();
+        errors.extend(iter);
+        errors
+    }
+}
+
+/// Errors added as tuples have [`Severity::Error`].
+impl Extend<(SourcePath, BoxError)> for AccumulatedError {
+    fn extend<T: IntoIterator<Item = (SourcePath, BoxError)>>(&mut self, iter: T) {
+        for (path, error) in iter {
+            self.push_entry(Entry::new(path, Severity::Error, error, None));
+        }
+    }
+}
+
+/// Errors collected from tuples have [`Severity::Error`].
+impl FromIterator<(SourcePath, BoxError)> for AccumulatedError {
+    fn from_iter<T: IntoIterator<Item = (SourcePath, BoxError)>>(iter: T) -> Self {
+        let mut errors = Self::default();
+        errors.extend(iter);
+        errors
     }
 }
 
 impl fmt::Display for AccumulatedError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Accumulated errors:")?;
-        for (path, error) in &self.errors {
-            writeln!(f, "- {path}: {error}")?;
+        for entry @ Entry { path, error, .. } in &self.errors {
+            writeln!(f, "- {path}: {}{error}", entry.prefix())?;
+            entry.fmt_causes(f, 2)?;
+        }
+        self.fmt_suppressed(f)
+    }
+}
+
+impl Error for AccumulatedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.errors
+            .iter()
+            .find(|entry| entry.severity == Severity::Error)
+            .map(|entry| entry.error.as_ref() as _)
+    }
+}
+
+impl AccumulatedError {
+    fn fmt_suppressed(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.suppressed {
+            0 if self.truncated => writeln!(f, "- ... further errors were suppressed"),
+            0 => Ok(()),
+            1 => writeln!(f, "- ... and at least 1 more error was suppressed"),
+            n => writeln!(f, "- ... and at least {n} more errors were suppressed"),
+        }
+    }
+}
+
+impl Entry {
+    /// Unwraps [`Coded`] and [`Localized`] errors, the outermost code or
+    /// message wins.
+    fn new(
+        path: SourcePath,
+        severity: Severity,
+        mut error: BoxError,
+        mut type_name: Option<&'static str>,
+    ) -> Self {
+        let mut code = None;
+        let mut message = None;
+        loop {
+            error = match error.downcast::<Coded>() {
+                Ok(coded) => {
+                    code = code.or(Some(coded.code));
+                    type_name = Some(coded.type_name);
+                    coded.error
+                }
+                Err(error) => match error.downcast::<Localized>() {
+                    Ok(localized) => {
+                        let (inner_message, inner, inner_type_name) = localized.into_parts();
+                        message = message.or(Some(inner_message));
+                        type_name = Some(inner_type_name);
+                        inner
+                    }
+                    Err(error) => {
+                        return Self {
+                            path,
+                            severity,
+                            code,
+                            message,
+                            error,
+                            type_name,
+                        };
+                    }
+                },
+            };
+        }
+    }
+
+    /// The path of the error.
+    pub fn path(&self) -> &SourcePath {
+        &self.path
+    }
+
+    /// The severity of the error.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// The [code](ErrorCode) of the error, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// The [message key](Localize) of the error, if any.
+    pub fn message_key(&self) -> Option<&str> {
+        self.message.as_ref().map(i18n::Message::key)
+    }
+
+    /// The message arguments of the error, if it has a message key.
+    pub fn message_args(&self) -> Option<&MessageArgs> {
+        self.message.as_ref().map(i18n::Message::args)
+    }
+
+    /// The error.
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self.error.as_ref()
+    }
+
+    /// The path and the error, dropping severity, code and message key.
+    pub fn into_parts(self) -> (SourcePath, BoxError) {
+        (self.path, self.error)
+    }
+
+    /// Marks non-fatal errors and the code when displayed.
+    fn prefix(&self) -> String {
+        let severity = match self.severity {
+            Severity::Info => "info: ",
+            Severity::Warning => "warning: ",
+            Severity::Error => "",
+        };
+        match &self.code {
+            Some(code) => format!("{severity}[{code}] "),
+            None => severity.to_string(),
+        }
+    }
+
+    /// Write the sources of the error indented by `indent` in the alternate
+    /// format.
+    fn fmt_causes(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        if f.alternate() {
+            for cause in Chain::new(self.error.as_ref()).skip(1) {
+                writeln!(f, "{:indent$}caused by: {cause}", "")?;
+            }
         }
         Ok(())
     }
 }
 
-impl Error for AccumulatedError {}
+impl<'a> Chain<'a> {
+    /// Construct the chain of `error` and its sources.
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        Self { next: Some(error) }
+    }
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let error = self.next?;
+        self.next = error.source();
+        Some(error)
+    }
+}
+
+impl FusedIterator for Chain<'_> {}
+
+impl Coded {
+    /// Attach `code` to `error`.
+    pub fn new<E>(code: impl Into<Cow<'static, str>>, error: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        Self {
+            code: code.into(),
+            error: Box::new(error),
+            type_name: any::type_name::<E>(),
+        }
+    }
+
+    /// Attach the [`ErrorCode`] of `error`.
+    pub fn from_error<E>(error: E) -> Self
+    where
+        E: ErrorCode + Send + Sync + 'static,
+    {
+        Self::new(error.code(), error)
+    }
+
+    /// Attach the [`ErrorCode`] and the message key and arguments of `error`.
+    pub(crate) fn localized<E>(error: E) -> Self
+    where
+        E: ErrorCode + Localize + Send + Sync + 'static,
+    {
+        Self::new(error.code(), Localized::from_error(error))
+    }
+
+    /// The attached code.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The wrapped error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error
+    }
+}
+
+impl fmt::Display for Coded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl Error for Coded {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl ErrorCode for MissingField {
+    fn code(&self) -> Cow<'static, str> {
+        "missing_field".into()
+    }
+}
+
+impl ErrorCode for UnknownVariant {
+    fn code(&self) -> Cow<'static, str> {
+        "unknown_variant".into()
+    }
+}
+
+impl Localize for MissingField {
+    fn message_key(&self) -> Cow<'static, str> {
+        "missing_field".into()
+    }
+}
+
+impl Localize for UnknownVariant {
+    fn message_key(&self) -> Cow<'static, str> {
+        "unknown_variant".into()
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new()
+            .with("tag", self.tag.clone())
+            .with("allowed", self.allowed.join(", "))
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl fmt::Display for DisplayJsonPointers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Accumulated errors:")?;
+        for entry @ Entry { path, error, .. } in &self.0.errors {
+            writeln!(f, "- {}: {}{error}", path.json_pointer(), entry.prefix())?;
+            entry.fmt_causes(f, 2)?;
+        }
+        self.0.fmt_suppressed(f)
+    }
+}
+
+impl fmt::Display for DisplayWithSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Accumulated errors:")?;
+        for entry @ Entry { path, error, .. } in &self.errors.errors {
+            let name = self.source.name();
+            let prefix = entry.prefix();
+            match self.source.location(path) {
+                Some(location) => writeln!(f, "- {name}:{location}: {path}: {prefix}{error}")?,
+                None => writeln!(f, "- {name}: {path}: {prefix}{error}")?,
+            }
+            entry.fmt_causes(f, 2)?;
+        }
+        self.errors.fmt_suppressed(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AccumulatedError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(&self.errors)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Entry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut entry = serializer.serialize_struct("Entry", 6)?;
+        entry.serialize_field("path", &self.path.to_string())?;
+        entry.serialize_field("segments", &self.path)?;
+        entry.serialize_field("severity", &self.severity)?;
+        entry.serialize_field("code", &self.code)?;
+        entry.serialize_field("message", &self.error.to_string())?;
+        entry.serialize_field("type", &self.type_name)?;
+        entry.end()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -84,11 +923,11 @@ mod tests {
             index: 2,
         });
         let mut error = AccumulatedError::default();
-        error.append(
+        error.push(
             path1.clone(),
             io::Error::new(io::ErrorKind::Interrupted, "error1"),
         );
-        error.append(
+        error.push(
             path2.clone(),
             io::Error::new(io::ErrorKind::AlreadyExists, "error2"),
         );
@@ -99,4 +938,229 @@ mod tests {
         assert!(display.contains(&path1.to_string()));
         assert!(display.contains(&path2.to_string()));
     }
+
+    fn field(name: &str) -> SourcePath {
+        SourcePath::new().join(PathSegment::field(n(name)))
+    }
+
+    #[test]
+    fn should_round_trip_owned_errors() {
+        let mut error = AccumulatedError::default();
+        error.push(field("foo"), MissingField);
+        error.push(field("bar"), io::Error::other("error"));
+
+        error.push_with_severity(field("baz"), Severity::Warning, io::Error::other("old"));
+        error.push(
+            field("qux"),
+            Coded::new(
+                "E1",
+                Localized::new("qux", MessageArgs::new(), MissingField),
+            ),
+        );
+
+        let collected = error.into_iter().collect::<AccumulatedError>();
+
+        assert_eq!(collected.len(), 4);
+        assert_eq!(collected.get_by_type::<MissingField>().count(), 2);
+        assert_eq!(collected.iter().next().unwrap().0, &field("foo"));
+        assert_eq!(
+            collected
+                .get_by_severity(Severity::Warning)
+                .next()
+                .unwrap()
+                .0,
+            &field("baz")
+        );
+        assert_eq!(collected.get_by_code("E1").next().unwrap().0, &field("qux"));
+        let entry = collected.into_iter().last().unwrap();
+        assert_eq!(entry.message_key(), Some("qux"));
+        assert_eq!(entry.severity(), Severity::Error);
+    }
+
+    #[test]
+    fn should_drain_and_partition_errors() {
+        let mut error = AccumulatedError::default();
+        error.push(field("foo"), MissingField);
+        error.push(field("bar"), MissingField);
+        error.push(field("foo"), io::Error::other("error"));
+
+        let drained = error.drain_by_path(&field("foo")).collect::<Vec<_>>();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(error.len(), 1);
+
+        error.extend(drained);
+        let (missing, rest) = error.partition_by_type::<MissingField>();
+        assert_eq!(
+            missing,
+            vec![(field("bar"), MissingField), (field("foo"), MissingField)]
+        );
+        assert_eq!(rest.len(), 1);
+    }
+
+    #[test]
+    fn should_query_subtree() {
+        let hosts = SourcePath::new().join(PathSegment::array(n("hosts"), 2));
+        let url = hosts.join(PathSegment::field(n("url")));
+        let mut error = AccumulatedError::default();
+        error.push(hosts.clone(), MissingField);
+        error.push(url.clone(), MissingField);
+        error.push(url.clone(), io::Error::other("error"));
+        error.push(field("hosts"), MissingField);
+
+        assert_eq!(error.get_under(&hosts).count(), 3);
+        assert_eq!(error.paths().len(), 3);
+        assert_eq!(error.group_by_path()[&url].len(), 2);
+
+        let subtree = error.subtree(&hosts);
+        assert_eq!(subtree.len(), 3);
+        assert_eq!(subtree.get_by_path(&SourcePath::new()).count(), 1);
+        assert_eq!(subtree.get_by_path(&field("url")).count(), 2);
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("invalid port")]
+    struct InvalidPort(#[source] std::num::ParseIntError);
+
+    #[test]
+    fn should_walk_source_chains() {
+        let mut error = AccumulatedError::default();
+        error.push_with_severity(SourcePath::new(), Severity::Warning, MissingField);
+        error.push(
+            SourcePath::new().join(PathSegment::field(n("port"))),
+            InvalidPort("http".parse::<u16>().unwrap_err()),
+        );
+
+        let chains = error
+            .iter_chains()
+            .map(|(path, chain)| (path.to_string(), chain.map(|e| e.to_string()).collect()))
+            .collect::<Vec<(_, Vec<_>)>>();
+        assert_eq!(chains[0].1, ["required field is missing"]);
+        assert_eq!(
+            chains[1].1,
+            ["invalid port", "invalid digit found in string"]
+        );
+        assert_eq!(error.source().unwrap().to_string(), "invalid port");
+        assert_eq!(
+            error.to_string(),
+            "Accumulated errors:\n\
+             - root: warning: required field is missing\n\
+             - port: invalid port\n"
+        );
+        assert_eq!(
+            format!("{error:#}"),
+            "Accumulated errors:\n\
+             - root: warning: required field is missing\n\
+             - port: invalid port\n  \
+             caused by: invalid digit found in string\n"
+        );
+        assert_eq!(
+            format!("{:#}", error.display_tree()),
+            "Accumulated errors:\n\
+             root: warning: required field is missing\n\
+             port: invalid port\n  \
+             caused by: invalid digit found in string\n"
+        );
+    }
+
+    #[test]
+    fn should_display_json_pointers() {
+        let path = SourcePath::new()
+            .join(PathSegment::array(n("hosts"), 1))
+            .join(PathSegment::field(n("url")));
+        let mut error = AccumulatedError::default();
+        error.push(path, MissingField);
+
+        assert_eq!(
+            error.display_json_pointers().to_string(),
+            "Accumulated errors:\n- /hosts/1/url: required field is missing\n"
+        );
+    }
+
+    #[test]
+    fn should_display_with_source() {
+        let source =
+            SourceMap::from_json("config.json", "{\n  \"hosts\": [\n    {}\n  ]\n}").unwrap();
+        let mut error = AccumulatedError::default();
+        error.push(
+            SourcePath::new()
+                .join(PathSegment::array(n("hosts"), 0))
+                .join(PathSegment::field(n("url"))),
+            MissingField,
+        );
+        error.push(SourcePath::new(), MissingField);
+
+        assert_eq!(
+            error.display_with_source(&source).to_string(),
+            "Accumulated errors:\n\
+             - config.json:3:5: hosts[0].url: required field is missing\n\
+             - config.json:1:1: root: required field is missing\n"
+        );
+        assert_eq!(
+            error
+                .display_with_source(&SourceMap::new("empty.json", ""))
+                .to_string(),
+            "Accumulated errors:\n\
+             - empty.json: hosts[0].url: required field is missing\n\
+             - empty.json: root: required field is missing\n"
+        );
+    }
+
+    #[test]
+    fn should_record_codes() {
+        let mut error = AccumulatedError::default();
+        error.push(field("foo"), Coded::from_error(MissingField));
+        error.push_with_code(field("bar"), "E0042", io::Error::other("error"));
+        error.push(field("baz"), MissingField);
+        error.set_missing_codes("E0001".into());
+
+        assert_eq!(error.get_by_code("missing_field").count(), 1);
+        assert_eq!(error.get_by_code("E0001").count(), 1);
+        assert_eq!(error.get_by_type::<MissingField>().count(), 2);
+        assert_eq!(
+            error.to_string(),
+            "Accumulated errors:\n\
+             - foo: [missing_field] required field is missing\n\
+             - bar: [E0042] error\n\
+             - baz: [E0001] required field is missing\n"
+        );
+    }
+
+    #[test]
+    fn should_retain_errors() {
+        let mut error = AccumulatedError::default();
+        error.push(field("foo"), MissingField);
+        error.push(field("bar"), io::Error::other("error"));
+
+        error.retain(|path, _| path == &field("bar"));
+
+        assert_eq!(error.len(), 1);
+        assert_eq!(error.get_by_path(&field("bar")).count(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_to_json() {
+        let path = SourcePath::new()
+            .join(PathSegment::array(n("hosts"), 1))
+            .join(PathSegment::field(n("url")));
+        let mut error = AccumulatedError::default();
+        error.push(path, MissingField);
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "path": "hosts[1].url",
+                "segments": [
+                    { "array": { "name": "hosts", "index": 1 } },
+                    { "field": "url" },
+                ],
+                "severity": "error",
+                "code": null,
+                "message": "required field is missing",
+                "type": "error_accumulator::error::MissingField",
+            }])
+        );
+    }
 }
@@ -0,0 +1,464 @@
+//! Translate the messages of an [`AccumulatedError`] with a
+//! [`MessageCatalog`].
+
+use std::{borrow::Cow, error::Error, fmt};
+
+use super::{AccumulatedError, BoxError, Entry, Severity};
+
+#[cfg(feature = "fluent")]
+mod fluent;
+
+/// Errors with a message key and arguments to translate their message with a
+/// [`MessageCatalog`].
+///
+/// Wrap errors in [`Localized::from_error()`] when recording them to attach
+/// their key and arguments.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use error_accumulator::error::{Localize, MessageArgs};
+/// #[derive(Debug, thiserror::Error)]
+/// #[error("{actual} is not between {min} and {max}")]
+/// struct OutOfBounds {
+///     min: u16,
+///     max: u16,
+///     actual: u16,
+/// }
+///
+/// impl Localize for OutOfBounds {
+///     fn message_key(&self) -> Cow<'static, str> {
+///         "range.out_of_bounds".into()
+///     }
+///
+///     fn message_args(&self) -> MessageArgs {
+///         MessageArgs::new()
+///             .with("min", self.min)
+///             .with("max", self.max)
+///             .with("actual", self.actual)
+///     }
+/// }
+/// ```
+pub trait Localize: Error {
+    /// The key of the message, e.g. `range.out_of_bounds`.
+    fn message_key(&self) -> Cow<'static, str>;
+
+    /// The named arguments of the message.
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new()
+    }
+}
+
+/// An error with a message key and arguments attached.
+///
+/// Recording a `Localized` error stores the wrapped error with its key and
+/// arguments in the [`AccumulatedError`], i.e. the wrapper itself is not
+/// stored.
+#[derive(Debug)]
+pub struct Localized {
+    message: Message,
+    error: BoxError,
+    type_name: &'static str,
+}
+
+/// The named arguments of a localized message.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageArgs(Vec<(Cow<'static, str>, MessageArg)>);
+
+/// The value of an argument of a localized message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageArg {
+    /// A string.
+    String(Cow<'static, str>),
+    /// An integer.
+    Integer(i128),
+    /// A floating-point number.
+    Number(f64),
+}
+
+/// Translations of messages.
+///
+/// Errors are looked up by their [message key](Localize) or, if they have
+/// none, by their [code](super::ErrorCode), e.g. `missing_field`. Errors that
+/// are in neither are displayed untranslated.
+///
+/// With the `fluent` feature enabled, `FluentBundle`s are catalogs. Keys like
+/// `range.out_of_bounds` refer to the attribute `out_of_bounds` of the
+/// message `range`.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// use error_accumulator::{
+///     ErrorAccumulator,
+///     error::{MessageArgs, MessageCatalog},
+///     field,
+/// };
+///
+/// struct German(HashMap<&'static str, &'static str>);
+///
+/// impl MessageCatalog for German {
+///     fn format(&self, key: &str, _args: &MessageArgs) -> Option<String> {
+///         self.0.get(key).map(|message| message.to_string())
+///     }
+/// }
+///
+/// let catalog = German(HashMap::from([("missing_field", "Pflichtfeld fehlt")]));
+/// let errors = ErrorAccumulator::new()
+///     .required_field(field!("url"), None::<Result<String, std::fmt::Error>>)
+///     .analyse()
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     errors.display_localized(&catalog).to_string(),
+///     "- url: [missing_field] Pflichtfeld fehlt\n"
+/// );
+/// ```
+pub trait MessageCatalog {
+    /// Format the message with the given key, `None` if the catalog doesn't
+    /// contain it.
+    fn format(&self, key: &str, args: &MessageArgs) -> Option<String>;
+}
+
+/// Displays an [`AccumulatedError`] like [`Display`](fmt::Display) but with
+/// the messages translated by a [`MessageCatalog`].
+///
+/// Unlike `Display` there is no heading. The markers of warnings and infos
+/// and the line about suppressed errors are translated as well, see the
+/// associated constants for their keys.
+///
+/// See [`AccumulatedError::display_localized()`].
+#[derive(Clone, Copy)]
+pub struct DisplayLocalized<'a> {
+    errors: &'a AccumulatedError,
+    catalog: &'a dyn MessageCatalog,
+}
+
+#[derive(Debug)]
+pub(super) struct Message {
+    key: Cow<'static, str>,
+    args: MessageArgs,
+}
+
+impl Message {
+    pub(super) fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub(super) fn args(&self) -> &MessageArgs {
+        &self.args
+    }
+}
+
+impl Localized {
+    /// Attach the message `key` and its `args` to `error`.
+    pub fn new<E>(key: impl Into<Cow<'static, str>>, args: MessageArgs, error: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        Self {
+            message: Message {
+                key: key.into(),
+                args,
+            },
+            error: Box::new(error),
+            type_name: std::any::type_name::<E>(),
+        }
+    }
+
+    /// Attach the message key and arguments of `error`.
+    pub fn from_error<E>(error: E) -> Self
+    where
+        E: Localize + Send + Sync + 'static,
+    {
+        Self::new(error.message_key(), error.message_args(), error)
+    }
+
+    /// The attached message key.
+    pub fn key(&self) -> &str {
+        &self.message.key
+    }
+
+    /// The attached message arguments.
+    pub fn args(&self) -> &MessageArgs {
+        &self.message.args
+    }
+
+    /// The wrapped error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error
+    }
+
+    pub(super) fn into_parts(self) -> (Message, BoxError, &'static str) {
+        (self.message, self.error, self.type_name)
+    }
+}
+
+impl fmt::Display for Localized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl Error for Localized {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl MessageArgs {
+    /// Construct empty arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the argument `name`.
+    pub fn with(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<MessageArg>,
+    ) -> Self {
+        self.0.push((name.into(), value.into()));
+        self
+    }
+
+    /// Get the argument `name`.
+    pub fn get(&self, name: &str) -> Option<&MessageArg> {
+        self.iter()
+            .find_map(|(arg, value)| (arg == name).then_some(value))
+    }
+
+    /// Iterate over all arguments in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &MessageArg)> {
+        self.0.iter().map(|(name, value)| (name.as_ref(), value))
+    }
+
+    /// Whether there are no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for MessageArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageArg::String(value) => f.write_str(value),
+            MessageArg::Integer(value) => value.fmt(f),
+            MessageArg::Number(value) => value.fmt(f),
+        }
+    }
+}
+
+impl From<&'static str> for MessageArg {
+    fn from(value: &'static str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for MessageArg {
+    fn from(value: String) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<Cow<'static, str>> for MessageArg {
+    fn from(value: Cow<'static, str>) -> Self {
+        Self::String(value)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($variant:ident as $target:ty: $($num:ty),*) => {
+        $(
+            impl From<$num> for MessageArg {
+                fn from(value: $num) -> Self {
+                    Self::$variant(<$target>::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(Integer as i128: i8, i16, i32, i64, u8, u16, u32, u64);
+impl_from_number!(Number as f64: f32, f64);
+
+impl From<isize> for MessageArg {
+    fn from(value: isize) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<usize> for MessageArg {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl DisplayLocalized<'_> {
+    /// Key of the marker of warnings, `warning` if it is not in the catalog.
+    pub const WARNING_KEY: &'static str = "severity_warning";
+    /// Key of the marker of infos, `info` if it is not in the catalog.
+    pub const INFO_KEY: &'static str = "severity_info";
+    /// Key of the line about suppressed errors with the argument `count`, a
+    /// lower bound of the number of suppressed errors.
+    pub const SUPPRESSED_KEY: &'static str = "suppressed_errors";
+    /// Key of the line about skipped input if no errors were counted as
+    /// suppressed, see [`AccumulatedError::is_truncated()`].
+    pub const TRUNCATED_KEY: &'static str = "further_errors_suppressed";
+
+    fn translate(&self, key: &str, args: &MessageArgs, fallback: impl fmt::Display) -> String {
+        self.catalog
+            .format(key, args)
+            .unwrap_or_else(|| fallback.to_string())
+    }
+
+    fn fmt_entry(&self, f: &mut fmt::Formatter<'_>, entry: &Entry) -> fmt::Result {
+        let empty = MessageArgs::new();
+        match entry.severity {
+            Severity::Info => write!(f, "{}: ", self.translate(Self::INFO_KEY, &empty, "info"))?,
+            Severity::Warning => write!(
+                f,
+                "{}: ",
+                self.translate(Self::WARNING_KEY, &empty, "warning")
+            )?,
+            Severity::Error => {}
+        }
+        if let Some(code) = &entry.code {
+            write!(f, "[{code}] ")?;
+        }
+        let message = match (&entry.message, &entry.code) {
+            (Some(Message { key, args }), _) => self.translate(key, args, &entry.error),
+            (None, Some(code)) => self.translate(code, &empty, &entry.error),
+            (None, None) => entry.error.to_string(),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl<'a> DisplayLocalized<'a> {
+    pub(super) fn new(errors: &'a AccumulatedError, catalog: &'a dyn MessageCatalog) -> Self {
+        Self { errors, catalog }
+    }
+}
+
+impl fmt::Debug for DisplayLocalized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DisplayLocalized")
+            .field("errors", self.errors)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for DisplayLocalized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.errors.errors {
+            write!(f, "- {}: ", entry.path)?;
+            self.fmt_entry(f, entry)?;
+            f.write_str("\n")?;
+            entry.fmt_causes(f, 2)?;
+        }
+        let line = if self.errors.suppressed > 0 {
+            let args = MessageArgs::new().with("count", self.errors.suppressed);
+            self.catalog.format(Self::SUPPRESSED_KEY, &args)
+        } else if self.errors.truncated {
+            self.catalog
+                .format(Self::TRUNCATED_KEY, &MessageArgs::new())
+        } else {
+            None
+        };
+        match line {
+            Some(line) => writeln!(f, "- ... {line}")?,
+            None => self.errors.fmt_suppressed(f)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        error::{Coded, MissingField},
+        path::SourcePath,
+    };
+
+    /// Replaces `{name}` with the arguments.
+    struct Catalog(HashMap<&'static str, &'static str>);
+
+    impl MessageCatalog for Catalog {
+        fn format(&self, key: &str, args: &MessageArgs) -> Option<String> {
+            let mut message = self.0.get(key)?.to_string();
+            for (name, value) in args.iter() {
+                message = message.replace(&format!("{{{name}}}"), &value.to_string());
+            }
+            Some(message)
+        }
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("{actual} is not between {min} and {max}")]
+    struct OutOfBounds {
+        min: u16,
+        max: u16,
+        actual: u16,
+    }
+
+    impl Localize for OutOfBounds {
+        fn message_key(&self) -> Cow<'static, str> {
+            "range.out_of_bounds".into()
+        }
+
+        fn message_args(&self) -> MessageArgs {
+            MessageArgs::new()
+                .with("min", self.min)
+                .with("max", self.max)
+                .with("actual", self.actual)
+        }
+    }
+
+    #[test]
+    fn should_translate_messages() {
+        let catalog = Catalog(HashMap::from([
+            (
+                "range.out_of_bounds",
+                "{actual} liegt nicht zwischen {min} und {max}",
+            ),
+            ("missing_field", "Pflichtfeld fehlt"),
+            ("severity_warning", "Warnung"),
+            (
+                "further_errors_suppressed",
+                "weitere Fehler wurden ausgelassen",
+            ),
+        ]));
+        let mut errors = AccumulatedError::default();
+        errors.push(
+            "port".parse().unwrap(),
+            Coded::new(
+                "E1",
+                Localized::from_error(OutOfBounds {
+                    min: 1,
+                    max: 1024,
+                    actual: 8080,
+                }),
+            ),
+        );
+        errors.push_with_severity(
+            "host".parse().unwrap(),
+            Severity::Warning,
+            Coded::from_error(MissingField),
+        );
+        errors.push(SourcePath::new(), std::fmt::Error);
+        errors.set_truncated();
+
+        assert_eq!(errors.get_by_type::<OutOfBounds>().count(), 1);
+        assert_eq!(
+            errors.display_localized(&catalog).to_string(),
+            "\
+- port: [E1] 8080 liegt nicht zwischen 1 und 1024
+- host: Warnung: [missing_field] Pflichtfeld fehlt
+- root: an error occurred when formatting an argument
+- ... weitere Fehler wurden ausgelassen
+"
+        );
+    }
+}
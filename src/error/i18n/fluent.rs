@@ -0,0 +1,133 @@
+//! [`MessageCatalog`] of [`fluent_bundle`].
+
+use std::borrow::Borrow;
+
+use fluent_bundle::{
+    FluentArgs, FluentResource, FluentValue, bundle::FluentBundle, memoizer::MemoizerKind,
+};
+
+use super::{MessageArg, MessageArgs, MessageCatalog};
+
+/// Messages that fail to format, e.g. because of a missing argument, are
+/// treated as missing.
+impl<R, M> MessageCatalog for FluentBundle<R, M>
+where
+    R: Borrow<FluentResource>,
+    M: MemoizerKind,
+{
+    fn format(&self, key: &str, args: &MessageArgs) -> Option<String> {
+        let (id, attribute) = match key.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (key, None),
+        };
+        let message = self.get_message(id)?;
+        let pattern = match attribute {
+            Some(attribute) => message.get_attribute(attribute)?.value(),
+            None => message.value()?,
+        };
+        let args = args
+            .iter()
+            .map(|(name, value)| (name, FluentValue::from(value)))
+            .collect::<FluentArgs>();
+        let mut errors = Vec::new();
+        let formatted = self.format_pattern(pattern, Some(&args), &mut errors);
+        errors.is_empty().then(|| formatted.into_owned())
+    }
+}
+
+/// Fluent numbers are `f64`, so integers that don't fit losslessly are passed
+/// as strings.
+impl<'a> From<&'a MessageArg> for FluentValue<'a> {
+    fn from(value: &'a MessageArg) -> Self {
+        /// The largest integer up to which all integers are exact `f64`s.
+        const MAX_EXACT: u128 = 1 << f64::MANTISSA_DIGITS;
+
+        match value {
+            MessageArg::String(value) => FluentValue::from(value.as_ref()),
+            MessageArg::Integer(value) if value.unsigned_abs() <= MAX_EXACT => {
+                FluentValue::from(*value as f64)
+            }
+            MessageArg::Integer(value) => FluentValue::from(value.to_string()),
+            MessageArg::Number(value) => FluentValue::from(*value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::{AccumulatedError, Coded, MissingField, UnknownVariant},
+        path::SourcePath,
+    };
+
+    fn bundle() -> fluent_bundle::FluentBundle<FluentResource> {
+        let resource = FluentResource::try_new(
+            "\
+missing_field = Pflichtfeld fehlt
+unknown_variant = Unbekannte Variante { $tag }, erlaubt sind { $allowed }
+range = Wert ungültig
+    .out_of_bounds = { $actual } liegt nicht zwischen { $min } und { $max }
+"
+            .to_string(),
+        )
+        .unwrap();
+        let mut bundle = fluent_bundle::FluentBundle::default();
+        bundle.set_use_isolating(false);
+        bundle.add_resource(resource).unwrap();
+        bundle
+    }
+
+    #[test]
+    fn should_format_messages_and_attributes() {
+        let bundle = bundle();
+        let args = MessageArgs::new()
+            .with("min", 1)
+            .with("max", 1024)
+            .with("actual", 8080);
+
+        assert_eq!(
+            bundle.format("range.out_of_bounds", &args).as_deref(),
+            Some("8080 liegt nicht zwischen 1 und 1024")
+        );
+        assert_eq!(
+            bundle.format("range", &args).as_deref(),
+            Some("Wert ungültig")
+        );
+        assert_eq!(bundle.format("range.unknown", &args), None);
+
+        let args = MessageArgs::new()
+            .with("min", 0)
+            .with("max", i64::MAX)
+            .with("actual", u64::MAX);
+        assert_eq!(
+            bundle.format("range.out_of_bounds", &args).as_deref(),
+            Some("18446744073709551615 liegt nicht zwischen 0 und 9223372036854775807")
+        );
+        assert_eq!(
+            bundle.format("range.out_of_bounds", &MessageArgs::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn should_translate_builtin_errors() {
+        let mut errors = AccumulatedError::default();
+        errors.push(SourcePath::new(), Coded::localized(MissingField));
+        errors.push(
+            "kind".parse().unwrap(),
+            Coded::localized(UnknownVariant {
+                tag: "ftp".to_string(),
+                allowed: vec!["http", "https"],
+            }),
+        );
+
+        assert_eq!(
+            errors.display_localized(&bundle()).to_string(),
+            "\
+- root: [missing_field] Pflichtfeld fehlt
+- kind: [unknown_variant] Unbekannte Variante ftp, erlaubt sind http, https
+"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Render an [`AccumulatedError`] as problem details for HTTP APIs.
+
+use crate::error::{AccumulatedError, Severity};
+
+/// A problem details document as defined by
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457) (formerly RFC 7807).
+///
+/// Each recorded error with [`Severity::Error`] becomes an entry of the
+/// `invalid-params` extension.
+/// With the `serde` feature enabled the document serializes to the expected
+/// JSON format, see [`CONTENT_TYPE`](Self::CONTENT_TYPE).
+///
+/// ```
+/// # use error_accumulator::{ErrorAccumulator, error::ProblemDetails, field};
+/// let errors = ErrorAccumulator::new()
+///     .field(field!("port"), "http".parse::<u16>())
+///     .analyse()
+///     .unwrap_err();
+///
+/// let problem = ProblemDetails::new(&errors)
+///     .with_type("https://example.com/probs/invalid-config")
+///     .with_status(422);
+///
+/// assert_eq!(problem.invalid_params[0].name, "port");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type.
+    ///
+    /// Defaults to `about:blank`.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub problem_type: String,
+    /// Short, human-readable summary of the problem type.
+    ///
+    /// Defaults to `Bad Request`, the phrase of the default status, as
+    /// RFC 9457 recommends for the type `about:blank`.
+    pub title: String,
+    /// The HTTP status code.
+    ///
+    /// Defaults to `400`.
+    pub status: u16,
+    /// One entry per recorded error.
+    #[cfg_attr(feature = "serde", serde(rename = "invalid-params"))]
+    pub invalid_params: Vec<InvalidParam>,
+}
+
+/// An entry of the `invalid-params` extension of [`ProblemDetails`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InvalidParam {
+    /// The path of the invalid input.
+    pub name: String,
+    /// Why the input is invalid.
+    pub reason: String,
+    /// The [code](crate::error::ErrorCode) of the error, if any.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub code: Option<String>,
+}
+
+impl ProblemDetails {
+    /// The media type of problem details serialized as JSON.
+    pub const CONTENT_TYPE: &str = "application/problem+json";
+
+    /// Construct the problem details for the accumulated errors.
+    pub fn new(errors: &AccumulatedError) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: "Bad Request".to_string(),
+            status: 400,
+            invalid_params: errors
+                .errors
+                .iter()
+                .filter(|entry| entry.severity == Severity::Error)
+                .map(|entry| InvalidParam {
+                    name: entry.path.to_string(),
+                    reason: entry.error.to_string(),
+                    code: entry.code.as_ref().map(|code| code.to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Set the problem type.
+    pub fn with_type(mut self, problem_type: impl Into<String>) -> Self {
+        self.problem_type = problem_type.into();
+        self
+    }
+
+    /// Set the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the HTTP status code.
+    ///
+    /// Keep the [title](Self::with_title) in line with the status if the
+    /// problem type is `about:blank`, e.g. `Unprocessable Content` for `422`.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+impl From<&AccumulatedError> for ProblemDetails {
+    fn from(errors: &AccumulatedError) -> Self {
+        Self::new(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::{Coded, MissingField},
+        path::{PathSegment, SourcePath},
+        test_util::n,
+    };
+
+    fn errors() -> AccumulatedError {
+        let mut errors = AccumulatedError::default();
+        errors.push(
+            SourcePath::new()
+                .join(PathSegment::array(n("hosts"), 1))
+                .join(PathSegment::field(n("url"))),
+            Coded::from_error(MissingField),
+        );
+        errors
+    }
+
+    #[test]
+    fn should_list_invalid_params() {
+        let problem = ProblemDetails::new(&errors());
+
+        assert_eq!(problem.problem_type, "about:blank");
+        assert_eq!(problem.title, "Bad Request");
+        assert_eq!(problem.status, 400);
+        assert_eq!(
+            problem.invalid_params,
+            vec![InvalidParam {
+                name: "hosts[1].url".to_string(),
+                reason: "required field is missing".to_string(),
+                code: Some("missing_field".to_string()),
+            }]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_to_json() {
+        let problem = ProblemDetails::new(&errors())
+            .with_status(422)
+            .with_title("Unprocessable Content");
+
+        assert_eq!(
+            serde_json::to_value(&problem).unwrap(),
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Unprocessable Content",
+                "status": 422,
+                "invalid-params": [
+                    {
+                        "name": "hosts[1].url",
+                        "reason": "required field is missing",
+                        "code": "missing_field",
+                    },
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,444 @@
+//! Render an [`AccumulatedError`] as a tree of its paths.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    io::{self, IsTerminal},
+};
+
+use super::{AccumulatedError, Entry, Severity};
+use crate::path::{FieldName, PathSegment, write_name, write_quoted};
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Displays an [`AccumulatedError`] as an indented tree that groups the errors
+/// by the segments of their paths.
+///
+/// Names and indices of array and map segments are separate nodes, so all
+/// errors of `hosts[3]` are listed below `hosts` → `[3]`. The tree can be
+/// [sorted](Self::sorted), [cut off](Self::max_depth) at a depth,
+/// [truncated](Self::max_children) and [colored](Self::color).
+///
+/// ```
+/// use error_accumulator::{error::AccumulatedError, error::MissingField, path};
+///
+/// let mut errors = AccumulatedError::default();
+/// errors.push(path!("hosts[3].url"), MissingField);
+/// errors.push(path!("hosts[3].port"), MissingField);
+/// errors.push(path!("name"), MissingField);
+///
+/// assert_eq!(
+///     errors.display_tree().to_string(),
+///     "\
+/// Accumulated errors:
+/// hosts
+///   [3]
+///     url: required field is missing
+///     port: required field is missing
+/// name: required field is missing
+/// "
+/// );
+/// ```
+///
+/// See [`AccumulatedError::display_tree()`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayTree<'a> {
+    errors: &'a AccumulatedError,
+    sorted: bool,
+    max_depth: Option<usize>,
+    max_children: Option<usize>,
+    color: ColorChoice,
+}
+
+/// Whether to color a [`DisplayTree`] with ANSI escape codes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Color if the `NO_COLOR` environment variable is unset or empty and
+    /// stderr is a terminal.
+    Auto,
+    /// Always color.
+    Always,
+    /// Never color.
+    #[default]
+    Never,
+}
+
+/// A segment of a path as shown in the tree.
+///
+/// The order sorts fields by name and elements by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Label<'a> {
+    Name(&'a FieldName),
+    Index(usize),
+    Key(&'a str),
+}
+
+/// Labels displayed as a path, e.g. `[3].url`.
+struct Labels<'a>(&'a [Label<'a>]);
+
+#[derive(Debug, Default)]
+struct Node<'a> {
+    /// A single label unless the labels below the maximum depth are collapsed.
+    labels: Vec<Label<'a>>,
+    entries: Vec<&'a Entry>,
+    children: Vec<Node<'a>>,
+    /// Positions of the children by their labels.
+    index: HashMap<Vec<Label<'a>>, usize>,
+}
+
+struct Printer {
+    max_children: usize,
+    colored: bool,
+}
+
+impl<'a> DisplayTree<'a> {
+    pub(super) fn new(errors: &'a AccumulatedError) -> Self {
+        Self {
+            errors,
+            sorted: false,
+            max_depth: None,
+            max_children: None,
+            color: ColorChoice::Never,
+        }
+    }
+
+    /// Sort the nodes, fields by name and elements by index, instead of
+    /// keeping the order the errors were recorded in.
+    pub fn sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Collapse the segments below `depth` into a single node, e.g.
+    /// `hosts` → `[3].url` with a depth of `2`.
+    ///
+    /// A depth of `0` is treated as `1`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth.max(1));
+        self
+    }
+
+    /// Show at most `max` errors and child nodes per node and summarize the
+    /// rest as `... and 5 more errors`.
+    pub fn max_children(mut self, max: usize) -> Self {
+        self.max_children = Some(max);
+        self
+    }
+
+    /// Color the output with ANSI escape codes.
+    ///
+    /// Defaults to [`ColorChoice::Never`].
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && io::stderr().is_terminal()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+impl<'a> Label<'a> {
+    /// Arrays and maps are split into their name and the subscript.
+    fn of_segment(segment: &'a PathSegment) -> impl Iterator<Item = Self> {
+        let labels = match segment {
+            PathSegment::Field(name) => [Some(Label::Name(name)), None],
+            PathSegment::Array { name, index } => {
+                [Some(Label::Name(name)), Some(Label::Index(*index))]
+            }
+            PathSegment::Index(index) => [Some(Label::Index(*index)), None],
+            PathSegment::Key { name, key } => [Some(Label::Name(name)), Some(Label::Key(key))],
+        };
+        labels.into_iter().flatten()
+    }
+}
+
+impl<'a> Node<'a> {
+    fn insert(&mut self, labels: &[Label<'a>], entry: &'a Entry, max_depth: usize) {
+        if labels.is_empty() {
+            self.entries.push(entry);
+            return;
+        }
+        let (head, rest) = if max_depth <= 1 {
+            (labels, &[][..])
+        } else {
+            labels.split_at(1)
+        };
+        let idx = match self.index.get(head) {
+            Some(&idx) => idx,
+            None => {
+                self.index.insert(head.to_vec(), self.children.len());
+                self.children.push(Node {
+                    labels: head.to_vec(),
+                    ..Node::default()
+                });
+                self.children.len() - 1
+            }
+        };
+        self.children[idx].insert(rest, entry, max_depth - 1);
+    }
+
+    /// Sort the children, insert no further errors afterwards.
+    fn sort(&mut self) {
+        self.children.sort_by(|a, b| a.labels.cmp(&b.labels));
+        self.index.clear();
+        self.children.iter_mut().for_each(Node::sort);
+    }
+
+    /// Number of errors in the subtree.
+    fn count(&self) -> usize {
+        self.entries.len() + self.children.iter().map(Node::count).sum::<usize>()
+    }
+}
+
+impl Printer {
+    fn paint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        style: &str,
+        content: impl fmt::Display,
+    ) -> fmt::Result {
+        if self.colored {
+            write!(f, "{style}{content}{RESET}")
+        } else {
+            write!(f, "{content}")
+        }
+    }
+
+    fn fmt_entry(&self, f: &mut fmt::Formatter<'_>, entry: &Entry, indent: usize) -> fmt::Result {
+        let style = match entry.severity {
+            Severity::Info => CYAN,
+            Severity::Warning => YELLOW,
+            Severity::Error => RED,
+        };
+        self.paint(f, style, format_args!("{}{}", entry.prefix(), entry.error))?;
+        f.write_char('\n')?;
+        entry.fmt_causes(f, indent + 2)
+    }
+
+    /// Write the errors and children of `node`, the errors of the root are
+    /// labeled as `root`.
+    fn fmt_children(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        node: &Node<'_>,
+        depth: usize,
+    ) -> fmt::Result {
+        let indent = depth * 2;
+        let shown_children = self.max_children.saturating_sub(node.entries.len());
+        for entry in node.entries.iter().take(self.max_children) {
+            write!(f, "{:indent$}", "")?;
+            if depth == 0 {
+                self.paint(f, BOLD, "root")?;
+                f.write_str(": ")?;
+            } else {
+                f.write_str("- ")?;
+            }
+            self.fmt_entry(f, entry, indent)?;
+        }
+        for child in node.children.iter().take(shown_children) {
+            write!(f, "{:indent$}", "")?;
+            self.paint(f, BOLD, Labels(&child.labels))?;
+            match child.entries.as_slice() {
+                [entry] if child.children.is_empty() => {
+                    f.write_str(": ")?;
+                    self.fmt_entry(f, entry, indent)?;
+                }
+                _ => {
+                    f.write_char('\n')?;
+                    self.fmt_children(f, child, depth + 1)?;
+                }
+            }
+        }
+        let hidden = node.entries.len().saturating_sub(self.max_children)
+            + node
+                .children
+                .iter()
+                .skip(shown_children)
+                .map(Node::count)
+                .sum::<usize>();
+        if hidden > 0 {
+            write!(f, "{:indent$}", "")?;
+            let plural = if hidden == 1 { "" } else { "s" };
+            self.paint(f, DIM, format_args!("... and {hidden} more error{plural}"))?;
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DisplayTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_depth = self.max_depth.unwrap_or(usize::MAX);
+        let mut root = Node::default();
+        for entry in &self.errors.errors {
+            let labels = entry
+                .path
+                .iter()
+                .flat_map(Label::of_segment)
+                .collect::<Vec<_>>();
+            root.insert(&labels, entry, max_depth);
+        }
+        if self.sorted {
+            root.sort();
+        }
+
+        let printer = Printer {
+            max_children: self.max_children.unwrap_or(usize::MAX),
+            colored: self.color.enabled(),
+        };
+        writeln!(f, "Accumulated errors:")?;
+        printer.fmt_children(f, &root, 0)?;
+        self.errors.fmt_suppressed(f)
+    }
+}
+
+impl fmt::Display for Label<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Label::Name(name) => write_name(f, name),
+            Label::Index(index) => write!(f, "[{index}]"),
+            Label::Key(key) => {
+                f.write_char('[')?;
+                write_quoted(f, key)?;
+                f.write_char(']')
+            }
+        }
+    }
+}
+
+impl fmt::Display for Labels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, label) in self.0.iter().enumerate() {
+            if idx > 0 && matches!(label, Label::Name(_)) {
+                f.write_char('.')?;
+            }
+            write!(f, "{label}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Coded, MissingField};
+
+    fn errors() -> AccumulatedError {
+        let mut errors = AccumulatedError::default();
+        for path in [
+            "hosts[3].url",
+            "name",
+            "hosts[1].url",
+            "hosts[3].port",
+            r#"labels["app.kubernetes.io/name"]"#,
+        ] {
+            errors.push(path.parse().unwrap(), MissingField);
+        }
+        errors.push(Default::default(), Coded::from_error(MissingField));
+        errors.push_with_severity("hosts[1]".parse().unwrap(), Severity::Warning, MissingField);
+        errors
+    }
+
+    #[test]
+    fn should_group_by_segments() {
+        assert_eq!(
+            errors().display_tree().to_string(),
+            r#"Accumulated errors:
+root: [missing_field] required field is missing
+hosts
+  [3]
+    url: required field is missing
+    port: required field is missing
+  [1]
+    - warning: required field is missing
+    url: required field is missing
+name: required field is missing
+labels
+  ["app.kubernetes.io/name"]: required field is missing
+"#
+        );
+    }
+
+    #[test]
+    fn should_sort_and_collapse() {
+        assert_eq!(
+            errors()
+                .display_tree()
+                .sorted(true)
+                .max_depth(2)
+                .to_string(),
+            r#"Accumulated errors:
+root: [missing_field] required field is missing
+hosts
+  [1]: warning: required field is missing
+  [1].url: required field is missing
+  [3].port: required field is missing
+  [3].url: required field is missing
+labels
+  ["app.kubernetes.io/name"]: required field is missing
+name: required field is missing
+"#
+        );
+    }
+
+    #[test]
+    fn should_truncate_children() {
+        let mut errors = AccumulatedError::default();
+        for idx in 0..5 {
+            errors.push(format!("hosts[{idx}].url").parse().unwrap(), MissingField);
+            errors.push(format!("hosts[{idx}].port").parse().unwrap(), MissingField);
+        }
+
+        assert_eq!(
+            errors
+                .display_tree()
+                .max_depth(2)
+                .max_children(2)
+                .to_string(),
+            "\
+Accumulated errors:
+hosts
+  [0].url: required field is missing
+  [0].port: required field is missing
+  ... and 8 more errors
+"
+        );
+    }
+
+    #[test]
+    fn should_group_many_siblings() {
+        let mut errors = AccumulatedError::default();
+        for idx in 0..100_000 {
+            errors.push(format!("hosts[{idx}]").parse().unwrap(), MissingField);
+        }
+
+        let tree = errors.display_tree().max_children(1).to_string();
+        assert!(tree.ends_with("  ... and 99999 more errors\n"), "{tree}");
+    }
+
+    #[test]
+    fn should_color() {
+        let mut errors = AccumulatedError::default();
+        errors.push("port".parse().unwrap(), MissingField);
+
+        assert_eq!(
+            errors.display_tree().color(ColorChoice::Always).to_string(),
+            "Accumulated errors:\n\x1b[1mport\x1b[0m: \x1b[31mrequired field is missing\x1b[0m\n"
+        );
+    }
+}
@@ -16,26 +16,33 @@
 use std::{error::Error, marker::PhantomData};
 
 use crate::{
-    builder::{ArrayBuilder, ErrorBuilderParent, FieldBuilder, StructBuilder},
+    builder::{
+        ArrayBuilder, ErrorBuilderParent, FieldBuilder, MapBuilder, StructBuilder, VariantBuilder,
+    },
     cons::{Append, AsRefTuple, Nil, ToTuple},
     construct::{Constructor, ListValidator},
-    error::AccumulatedError,
+    error::{AccumulatedError, Coded, MissingField, Severity},
     path::{FieldName, PathSegment, SourcePath},
 };
 
+pub mod accumulate;
 pub mod builder;
 mod cons;
 pub mod construct;
+#[cfg(feature = "serde")]
+pub mod de;
 pub mod error;
 pub mod path;
+pub mod source;
+pub mod validators;
 
 /// The entry-point to accumulate parsing results.
 ///
 /// All parsing results are tracked, i.e. `Ok` values and errors alike.
 ///
 /// Use the methods like [`field()`](Self::field), [`strukt()`](Self::strukt),
-/// or [`array()`](Self::array) to tell the accumulator from where in the input
-/// the next parsing results are derived.
+/// [`array()`](Self::array), or [`map()`](Self::map) to tell the accumulator
+/// from where in the input the next parsing results are derived.
 ///
 /// The final [`analyse()`](Self::analyse) call returns all errors if at least
 /// one error was recorded else a tuple of all recorded `Ok` values is returned.
@@ -106,6 +113,14 @@ where
             base,
         }
     }
+
+    fn record_warnings(&mut self, warnings: AccumulatedError) {
+        self.errors.merge(warnings);
+    }
+
+    fn remaining_errors(&self) -> Option<usize> {
+        self.errors.remaining()
+    }
 }
 
 impl<List> ErrorAccumulator<List> {
@@ -123,6 +138,101 @@ impl<List> ErrorAccumulator<List> {
         FieldBuilder::new(self, path).value(result).finish()
     }
 
+    /// Record a result of parsing a field of the input with the
+    /// [code](error::ErrorCode) of its error.
+    pub fn coded_field<FieldValue, E>(
+        self,
+        field: FieldName,
+        result: Result<FieldValue, E>,
+    ) -> ErrorAccumulator<List::Output>
+    where
+        List: Append<FieldValue>,
+        E: error::ErrorCode + Send + Sync + 'static,
+    {
+        let path = self.base.join(PathSegment::Field(field));
+        FieldBuilder::new(self, path).coded_value(result).finish()
+    }
+
+    /// Record a result of parsing a field of the input with the
+    /// [code](error::ErrorCode) and the [message key](error::Localize) of its
+    /// error.
+    pub fn localized_field<FieldValue, E>(
+        self,
+        field: FieldName,
+        result: Result<FieldValue, E>,
+    ) -> ErrorAccumulator<List::Output>
+    where
+        List: Append<FieldValue>,
+        E: error::ErrorCode + error::Localize + Send + Sync + 'static,
+    {
+        let path = self.base.join(PathSegment::Field(field));
+        FieldBuilder::new(self, path)
+            .localized_value(result)
+            .finish()
+    }
+
+    /// Record a value of a field of the input that is checked by a
+    /// [`Validator`](validators::Validator).
+    ///
+    /// See [`FieldBuilder::validated()`].
+    pub fn validated_field<FieldValue, V>(
+        self,
+        field: FieldName,
+        value: FieldValue,
+        validator: &V,
+    ) -> ErrorAccumulator<List::Output>
+    where
+        List: Append<FieldValue>,
+        V: validators::Validator<FieldValue> + ?Sized,
+    {
+        let path = self.base.join(PathSegment::Field(field));
+        FieldBuilder::new(self, path)
+            .validated(value, validator)
+            .finish()
+    }
+
+    /// Record a result of parsing a field that must be present in the input.
+    ///
+    /// If the field is missing, i.e. `result` is `None`, a [`MissingField`]
+    /// error is recorded.
+    pub fn required_field<FieldValue, E>(
+        self,
+        field: FieldName,
+        result: Option<Result<FieldValue, E>>,
+    ) -> ErrorAccumulator<List::Output>
+    where
+        List: Append<FieldValue>,
+        E: Error + Send + Sync + 'static,
+    {
+        let path = self.base.join(PathSegment::Field(field));
+        let builder = FieldBuilder::new(self, path);
+        match result {
+            Some(result) => builder.value(result).finish(),
+            None => builder
+                .value(Err::<FieldValue, _>(Coded::localized(MissingField)))
+                .finish(),
+        }
+    }
+
+    /// Record a result of parsing a field that may be missing in the input.
+    ///
+    /// The recorded value is `None` if the field is missing, i.e. `result` is
+    /// `None`.
+    pub fn optional_field<FieldValue, E>(
+        self,
+        field: FieldName,
+        result: Option<Result<FieldValue, E>>,
+    ) -> ErrorAccumulator<<List as Append<Option<FieldValue>>>::Output>
+    where
+        List: Append<Option<FieldValue>>,
+        E: Error + Send + Sync + 'static,
+    {
+        let path = self.base.join(PathSegment::Field(field));
+        FieldBuilder::new(self, path)
+            .value(result.transpose())
+            .finish()
+    }
+
     /// Start a [`FieldBuilder`] to record results for parsing of a single input
     /// field.
     ///
@@ -162,7 +272,44 @@ impl<List> ErrorAccumulator<List> {
         List: Append<Vec<ElementValue>>,
     {
         let base = self.base.clone();
-        ArrayBuilder::new(self, base, field)
+        ArrayBuilder::new(self, base, Some(field))
+    }
+
+    /// Start an [`ArrayBuilder`] to analyse the elements of an input that is an
+    /// array itself, e.g. a top-level JSON array.
+    ///
+    /// Errors are recorded with the element's index as path, e.g. `[2].url`.
+    pub fn elements<ElementValue>(self) -> ArrayBuilder<Self, ElementValue>
+    where
+        List: Append<Vec<ElementValue>>,
+    {
+        let base = self.base.clone();
+        ArrayBuilder::new(self, base, None)
+    }
+
+    /// Start a [`MapBuilder`] to analyse the entries of a nested map of the
+    /// input.
+    ///
+    /// See [`MapBuilder`] for more information.
+    pub fn map<Map>(self, field: FieldName) -> MapBuilder<Self, Map>
+    where
+        List: Append<Map>,
+        Map: Default,
+    {
+        let base = self.base.clone();
+        MapBuilder::new(self, base, field)
+    }
+
+    /// Start a [`VariantBuilder`] to analyse a nested tagged union of the
+    /// input.
+    ///
+    /// See [`VariantBuilder`] for more information.
+    pub fn variant<Value>(self, field: FieldName) -> VariantBuilder<Self, Value>
+    where
+        List: Append<Value>,
+    {
+        let path = self.base.join(PathSegment::Field(field));
+        VariantBuilder::new(self, path)
     }
 
     /// Run another validation step on the previously recorded `Ok` values if
@@ -183,7 +330,7 @@ impl<List> ErrorAccumulator<List> {
             base,
         } = self;
 
-        let values = if errors.is_empty() {
+        let values = if !errors.has_errors() {
             let result = validator.validate(&values);
             append_or_record(values, &base, result, &mut errors)
         } else {
@@ -197,6 +344,54 @@ impl<List> ErrorAccumulator<List> {
         }
     }
 
+    /// Stop recording errors after `max` errors, e.g. to keep reports of huge
+    /// inputs small.
+    ///
+    /// The limit applies to all nested builders. Further errors are only
+    /// counted, see [`AccumulatedError::suppressed()`], and [`ArrayBuilder`]
+    /// and [`MapBuilder`] skip their remaining elements, see
+    /// [`AccumulatedError::is_truncated()`]. As the skipped elements are never
+    /// parsed, the number of suppressed errors is a lower bound. At least one
+    /// error is recorded.
+    ///
+    /// ```
+    /// # use error_accumulator::{ErrorAccumulator, field};
+    /// let mut parsed = 0;
+    /// let errors = ErrorAccumulator::new()
+    ///     .max_errors(2)
+    ///     .array(field!("ids"))
+    ///     .of_values(["a", "b", "c", "d"].into_iter().map(|id| {
+    ///         parsed += 1;
+    ///         id.parse::<u32>()
+    ///     }))
+    ///     .finish()
+    ///     .analyse()
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(parsed, 3);
+    /// assert!(errors.is_truncated());
+    /// ```
+    pub fn max_errors(mut self, max: usize) -> Self {
+        self.errors.set_limit(max.max(1));
+        self
+    }
+
+    /// Record a warning for the input as a whole.
+    ///
+    /// Warnings are reported alongside errors but don't fail
+    /// [`analyse()`](Self::analyse). Use
+    /// [`analyse_with_warnings()`](Self::analyse_with_warnings) to get them in
+    /// case of success.
+    pub fn warn<W>(mut self, warning: W) -> Self
+    where
+        W: Error + Send + Sync + 'static,
+    {
+        self.errors
+            .push_with_severity(self.base.clone(), Severity::Warning, warning);
+        self
+    }
+
     /// Provide a [`Constructor`] function that is called on
     /// [`analyse()`](ErrorAccumulatorFinisher::analyse) in case all recorded
     /// results (including nested results) where [`Ok`].
@@ -219,18 +414,45 @@ impl<List> ErrorAccumulator<List> {
 
     /// Analyse all recorded results.
     ///
-    /// If at least one error was recorded the [`AccumulatedError`]s are
-    /// returned else a tuple of all recorded `Ok` values in recording order is
-    /// returned.
+    /// If at least one error with [`Severity::Error`] was recorded the
+    /// [`AccumulatedError`]s, including warnings, are returned else a tuple of
+    /// all recorded `Ok` values in recording order is returned.
     pub fn analyse(self) -> Result<List::List, AccumulatedError>
     where
         List: ToTuple,
     {
-        if self.errors.is_empty() {
-            // Would only panic if there were any errors.
-            Ok(self.values.unwrap_tuple())
-        } else {
+        self.analyse_with_warnings().map(|(values, _)| values)
+    }
+
+    /// Like [`analyse()`](Self::analyse) but the non-fatal errors, e.g.
+    /// warnings, are returned alongside the `Ok` values.
+    ///
+    /// ```
+    /// # use error_accumulator::{ErrorAccumulator, field};
+    /// #[derive(Debug, thiserror::Error)]
+    /// #[error("interval below 1s is discouraged")]
+    /// struct ShortInterval;
+    ///
+    /// let ((interval,), warnings) = ErrorAccumulator::new()
+    ///     .field_builder(field!("interval_ms"))
+    ///     .value("500".parse::<u64>())
+    ///     .warn(ShortInterval)
+    ///     .finish()
+    ///     .analyse_with_warnings()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(interval, 500);
+    /// assert_eq!(warnings.get_by_type::<ShortInterval>().count(), 1);
+    /// ```
+    pub fn analyse_with_warnings(self) -> Result<(List::List, AccumulatedError), AccumulatedError>
+    where
+        List: ToTuple,
+    {
+        if self.errors.has_errors() {
             Err(self.errors)
+        } else {
+            // Would only panic if there were any errors.
+            Ok((self.values.unwrap_tuple(), self.errors))
         }
     }
 }
@@ -243,11 +465,18 @@ where
     /// Like [`ErrorAccumulator::analyse()`] but the recorded `Ok` values are
     /// processed by the provided [`Constructor`].
     pub fn analyse(self) -> Result<Out, AccumulatedError> {
-        if self.accumulated_errors.is_empty() {
-            // Would only panic if there were any errors.
-            Ok(self.constructor.construct(self.values.unwrap_tuple()))
-        } else {
+        self.analyse_with_warnings().map(|(value, _)| value)
+    }
+
+    /// Like [`ErrorAccumulator::analyse_with_warnings()`] but the recorded `Ok`
+    /// values are processed by the provided [`Constructor`].
+    pub fn analyse_with_warnings(self) -> Result<(Out, AccumulatedError), AccumulatedError> {
+        if self.accumulated_errors.has_errors() {
             Err(self.accumulated_errors)
+        } else {
+            // Would only panic if there were any errors.
+            let value = self.constructor.construct(self.values.unwrap_tuple());
+            Ok((value, self.accumulated_errors))
         }
     }
 }
@@ -265,7 +494,7 @@ where
     match result {
         Ok(value) => list.append(value),
         Err(error) => {
-            errors.append(path.clone(), error);
+            errors.push(path.clone(), error);
             list.append(None)
         }
     }
@@ -282,7 +511,10 @@ pub(crate) mod test_util {
 
 #[cfg(test)]
 mod tests {
-    use std::num::{NonZeroI16, ParseIntError, TryFromIntError};
+    use std::{
+        io,
+        num::{NonZeroI16, ParseIntError, TryFromIntError},
+    };
 
     use super::*;
     use crate::test_util::n;
@@ -338,6 +570,113 @@ mod tests {
         assert_eq!(err.get_by_type::<TryFromIntError>().count(), 1);
     }
 
+    #[test]
+    fn should_propagate_warnings_of_nested_builders() {
+        let ((port, hosts), warnings) = ErrorAccumulator::new()
+            .strukt(n("server"))
+            .field_builder(n("port"))
+            .value("80".parse::<u16>())
+            .warn(io::Error::other("privileged port"))
+            .finish()
+            .on_ok(|port| port)
+            .finish()
+            .array(n("hosts"))
+            .of_values(["a", "b"].map(Ok::<_, io::Error>))
+            .warn(io::Error::other("deprecated"))
+            .finish()
+            .analyse_with_warnings()
+            .unwrap();
+
+        assert_eq!(port, 80);
+        assert_eq!(hosts, ["a", "b"]);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings.max_severity(), Some(Severity::Warning));
+        assert_eq!(
+            warnings.to_string(),
+            "Accumulated errors:\n\
+             - server.port: warning: privileged port\n\
+             - hosts: warning: deprecated\n"
+        );
+    }
+
+    #[test]
+    fn should_return_warnings_with_errors() {
+        let err = ErrorAccumulator::new()
+            .field_builder(n("foo"))
+            .value("foo".parse::<u32>())
+            .warn(io::Error::other("deprecated"))
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+        assert_eq!(err.get_by_severity(Severity::Error).count(), 1);
+        assert_eq!(err.get_by_severity(Severity::Warning).count(), 1);
+    }
+
+    #[cfg(feature = "large-tuples")]
+    #[test]
+    fn should_support_large_number_of_values() {
+        let sum = ErrorAccumulator::new()
+            .field(n("f1"), "1".parse::<u32>())
+            .field(n("f2"), "2".parse::<u32>())
+            .field(n("f3"), "3".parse::<u32>())
+            .field(n("f4"), "4".parse::<u32>())
+            .field(n("f5"), "5".parse::<u32>())
+            .field(n("f6"), "6".parse::<u32>())
+            .field(n("f7"), "7".parse::<u32>())
+            .field(n("f8"), "8".parse::<u32>())
+            .field(n("f9"), "9".parse::<u32>())
+            .field(n("f10"), "10".parse::<u32>())
+            .field(n("f11"), "11".parse::<u32>())
+            .field(n("f12"), "12".parse::<u32>())
+            .field(n("f13"), "13".parse::<u32>())
+            .field(n("f14"), "14".parse::<u32>())
+            .field(n("f15"), "15".parse::<u32>())
+            .field(n("f16"), "16".parse::<u32>())
+            .field(n("f17"), "17".parse::<u32>())
+            .field(n("f18"), "18".parse::<u32>())
+            .field(n("f19"), "19".parse::<u32>())
+            .field(n("f20"), "20".parse::<u32>())
+            .on_ok(|f1, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, f20| f1 + f20)
+            .analyse()
+            .unwrap();
+
+        assert_eq!(sum, 21);
+    }
+
+    #[test]
+    fn should_record_missing_required_field() {
+        let err = ErrorAccumulator::new()
+            .required_field(n("foo"), Some("42".parse::<u32>()))
+            .required_field(n("bar"), None::<Result<u32, ParseIntError>>)
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(err.len(), 1);
+        let (path, _) = err.get_by_type::<MissingField>().next().unwrap();
+        assert_eq!(path, &SourcePath::new().join(PathSegment::field(n("bar"))));
+    }
+
+    #[test]
+    fn should_record_optional_fields() {
+        let (foo, bar) = ErrorAccumulator::new()
+            .optional_field(n("foo"), Some("42".parse::<u32>()))
+            .optional_field(n("bar"), None::<Result<u32, ParseIntError>>)
+            .analyse()
+            .unwrap();
+
+        assert_eq!(foo, Some(42));
+        assert_eq!(bar, None);
+
+        let err = ErrorAccumulator::new()
+            .optional_field(n("foo"), Some("x".parse::<u32>()))
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(err.get_by_type::<ParseIntError>().count(), 1);
+    }
+
     #[test]
     fn should_allow_construction_on_success() {
         let thing = ErrorAccumulator::new()
@@ -1,15 +1,21 @@
 //! [`SourcePath`] to identify the path to the source of an accumulated error.
 
-use std::{borrow::Cow, fmt, num::ParseIntError, str::FromStr};
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+    num::ParseIntError,
+    str::FromStr,
+};
 
-const INVALID_FIELD_NAME_CHARS: [char; 3] = ['.', '[', ']'];
+/// Characters that require a [`FieldName`] to be quoted in a [`SourcePath`].
+const QUOTED_FIELD_NAME_CHARS: [char; 5] = ['.', '[', ']', '"', '\\'];
 
 /// Errors parsing a [`SourcePath`] or its components.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    /// Invalid [`FieldName`].
+    /// Unquoted [`FieldName`] that contains a character that requires quoting.
     #[error(
-        "failed to parse '{0}' as it contains at least one invalid character: {INVALID_FIELD_NAME_CHARS:?}"
+        "failed to parse '{0}' as it contains at least one character that requires quoting: {QUOTED_FIELD_NAME_CHARS:?}"
     )]
     InvalidCharInName(String),
     /// Incomplete array path.
@@ -18,18 +24,37 @@ pub enum Error {
     /// Invalid index in array path.
     #[error("invalid index")]
     InvalidIdx(#[from] ParseIntError),
+    /// Key of a map path is not properly quoted.
+    #[error("map segment '{0}' does not contain a properly quoted key")]
+    InvalidKeySegment(String),
+    /// A single [`PathSegment`] was expected but multiple were found.
+    #[error("'{0}' contains more than one segment")]
+    MultipleSegments(String),
+    /// Invalid JSON Pointer.
+    #[error("'{0}' is not a valid JSON Pointer")]
+    InvalidJsonPointer(String),
 }
 
 /// The full path to source of error from the input.
 ///
 /// Composed of [`PathSegment`]s.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct SourcePath {
     segments: Vec<PathSegment>,
 }
 
+/// Displays a [`SourcePath`] as JSON Pointer.
+///
+/// See [`SourcePath::json_pointer()`].
+#[derive(Debug, Clone, Copy)]
+pub struct JsonPointer<'a>(&'a SourcePath);
+
 /// A segment of a full [`SourcePath`].
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum PathSegment {
     /// The segment references a field.
     Field(FieldName),
@@ -40,13 +65,31 @@ pub enum PathSegment {
         /// The element's position within the array.
         index: usize,
     },
+    /// The segment references an element of an unnamed array, e.g. the
+    /// elements of a top-level array or of an array of arrays.
+    ///
+    /// Displayed as `[index]` without a leading `.`, e.g. `matrix[1][2]`. Use
+    /// [`PathSegment::Array`] for elements of named arrays as a field segment
+    /// followed by an index segment is displayed the same way.
+    Index(usize),
+    /// The segment references a value of a map.
+    ///
+    /// Displayed as `name["key"]` where `"` and `\` in the key are escaped
+    /// with a `\`, e.g. `labels["app.kubernetes.io/name"]`.
+    Key {
+        /// The map's name.
+        name: FieldName,
+        /// The value's key within the map.
+        key: String,
+    },
 }
 
-/// A valid name of an input's field.
+/// The name of an input's field.
 ///
-/// At the moment most characters are allowed excluding `.`, `[`, and `]`. This
-/// might change in the future.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Any string is a valid name. Names that are empty or contain any of `.`,
+/// `[`, `]`, `"` or `\` are quoted in the textual representation of a
+/// [`SourcePath`], e.g. `labels."app.kubernetes.io/name"`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FieldName(Cow<'static, str>);
 
 impl SourcePath {
@@ -62,14 +105,134 @@ impl SourcePath {
         new
     }
 
+    /// Append a new segment to the path in place.
+    pub fn push(&mut self, segment: PathSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Remove the last segment of the path.
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+
+    /// Append all segments of `other` to a copy of the path.
+    ///
+    /// For example: `foo.bar` concatenated with `baz[1]` is `foo.bar.baz[1]`.
+    pub fn concat(&self, other: &Self) -> Self {
+        let mut new = self.clone();
+        new.segments.extend_from_slice(&other.segments);
+        new
+    }
+
     /// Check if the other path has the same base as the path at hand.
     ///
     /// For example: `foo.bar` is the base of `foo.bar.baz`.
+    ///
+    /// Alias of [`starts_with()`](Self::starts_with).
     pub fn is_matching_base(&self, base: &Self) -> bool {
-        base.segments
-            .iter()
-            .zip(&self.segments)
-            .all(|(base, to_match)| base == to_match)
+        self.starts_with(base)
+    }
+
+    /// Check if `base` is a prefix of the path.
+    ///
+    /// Every path starts with the root path and with itself.
+    pub fn starts_with(&self, base: &Self) -> bool {
+        self.segments.starts_with(&base.segments)
+    }
+
+    /// The rest of the path after `prefix` or `None` if the path does not
+    /// start with `prefix`.
+    ///
+    /// For example: `foo.bar.baz` without the prefix `foo` is `bar.baz`.
+    pub fn strip_prefix(&self, prefix: &Self) -> Option<Self> {
+        self.segments
+            .strip_prefix(prefix.segments.as_slice())
+            .map(|rest| Self {
+                segments: rest.to_vec(),
+            })
+    }
+
+    /// The path without its last segment or `None` for the root path.
+    pub fn parent(&self) -> Option<Self> {
+        self.segments.split_last().map(|(_, parent)| Self {
+            segments: parent.to_vec(),
+        })
+    }
+
+    /// The last segment of the path or `None` for the root path.
+    pub fn last(&self) -> Option<&PathSegment> {
+        self.segments.last()
+    }
+
+    /// Number of segments of the path, `0` for the root path.
+    pub fn depth(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Convert the path into a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901),
+    /// e.g. `foo.bar[42].baz` into `/foo/bar/42/baz`.
+    ///
+    /// The root path is converted into the empty pointer `""`.
+    pub fn to_json_pointer(&self) -> String {
+        self.json_pointer().to_string()
+    }
+
+    /// Display the path as [JSON Pointer](Self::to_json_pointer()).
+    pub fn json_pointer(&self) -> JsonPointer<'_> {
+        JsonPointer(self)
+    }
+
+    /// Parse a path from a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901),
+    /// e.g. `/foo/bar/42/baz` into `foo.bar[42].baz`.
+    ///
+    /// JSON Pointers do not distinguish between fields, array indices and map
+    /// keys. A numeric token following a field is parsed as
+    /// [`PathSegment::Array`], other numeric tokens as [`PathSegment::Index`]
+    /// and all remaining tokens as fields.
+    pub fn from_json_pointer(pointer: &str) -> Result<Self, Error> {
+        let mut path = Self::new();
+        if pointer.is_empty() {
+            return Ok(path);
+        }
+
+        let tokens = pointer
+            .strip_prefix('/')
+            .ok_or_else(|| Error::InvalidJsonPointer(pointer.to_string()))?;
+        for token in tokens.split('/') {
+            let token = unescape_json_pointer_token(token)
+                .ok_or_else(|| Error::InvalidJsonPointer(pointer.to_string()))?;
+            // Array indices must not have leading zeros.
+            let is_index = token.bytes().all(|b| b.is_ascii_digit())
+                && (token == "0" || !token.starts_with('0'));
+            let index = is_index.then(|| token.parse::<usize>().ok()).flatten();
+            match (path.segments.last(), index) {
+                (Some(PathSegment::Field(_)), Some(index)) => {
+                    let Some(PathSegment::Field(name)) = path.segments.pop() else {
+                        unreachable!("last segment is a field");
+                    };
+                    path.segments.push(PathSegment::Array { name, index });
+                }
+                (_, Some(index)) => path.segments.push(PathSegment::Index(index)),
+                (_, None) => path
+                    .segments
+                    .push(PathSegment::Field(FieldName::new(token))),
+            }
+        }
+        Ok(path)
+    }
+
+    /// Iterate over the segments of the path from the root.
+    pub fn iter(&self) -> std::slice::Iter<'_, PathSegment> {
+        self.segments.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SourcePath {
+    type Item = &'a PathSegment;
+    type IntoIter = std::slice::Iter<'a, PathSegment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
@@ -82,21 +245,44 @@ impl fmt::Display for SourcePath {
             let start = segments.next().expect("segments is not empty");
             write!(f, "{start}")?;
             for segment in segments {
-                write!(f, ".{segment}")?;
+                match segment {
+                    PathSegment::Index(_) => write!(f, "{segment}")?,
+                    _ => write!(f, ".{segment}")?,
+                }
             }
             Ok(())
         }
     }
 }
 
+impl fmt::Display for JsonPointer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in self.0 {
+            match segment {
+                PathSegment::Field(name) => write_json_pointer_token(f, name.as_str())?,
+                PathSegment::Array { name, index } => {
+                    write_json_pointer_token(f, name.as_str())?;
+                    write!(f, "/{index}")?;
+                }
+                PathSegment::Index(index) => write!(f, "/{index}")?,
+                PathSegment::Key { name, key } => {
+                    write_json_pointer_token(f, name.as_str())?;
+                    write_json_pointer_token(f, key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl FromStr for SourcePath {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let segments = s
-            .split('.')
-            .map(|segment| segment.parse())
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut segments = Vec::new();
+        for segment in split_segments(s) {
+            parse_segments(segment, &mut segments)?;
+        }
         Ok(Self { segments })
     }
 }
@@ -111,13 +297,36 @@ impl PathSegment {
     pub fn array(name: FieldName, index: usize) -> Self {
         Self::Array { name, index }
     }
+
+    /// Construct a segment of an unnamed array.
+    pub fn index(index: usize) -> Self {
+        Self::Index(index)
+    }
+
+    /// Construct a map segment.
+    pub fn key(name: FieldName, key: impl Into<String>) -> Self {
+        Self::Key {
+            name,
+            key: key.into(),
+        }
+    }
 }
 
 impl fmt::Display for PathSegment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            PathSegment::Field(field) => write!(f, "{field}"),
-            PathSegment::Array { name, index } => write!(f, "{name}[{index}]"),
+            PathSegment::Field(field) => write_name(f, field),
+            PathSegment::Array { name, index } => {
+                write_name(f, name)?;
+                write!(f, "[{index}]")
+            }
+            PathSegment::Index(index) => write!(f, "[{index}]"),
+            PathSegment::Key { name, key } => {
+                write_name(f, name)?;
+                f.write_char('[')?;
+                write_quoted(f, key)?;
+                f.write_char(']')
+            }
         }
     }
 }
@@ -126,20 +335,12 @@ impl FromStr for PathSegment {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.ends_with(']') {
-            if let Some(idx) = s.find('[') {
-                let idx_str = &s[idx + 1..s.len() - 1];
-                let field_idx = idx_str.parse()?;
-                return Ok(Self::Array {
-                    name: s[..idx].parse()?,
-                    index: field_idx,
-                });
-            } else {
-                return Err(Error::IncompleteArraySegment(s.to_string()));
-            }
+        let mut segments = Vec::with_capacity(1);
+        parse_segments(s, &mut segments)?;
+        match <[_; 1]>::try_from(segments) {
+            Ok([segment]) => Ok(segment),
+            Err(_) => Err(Error::MultipleSegments(s.to_string())),
         }
-
-        Ok(Self::Field(s.parse()?))
     }
 }
 
@@ -150,7 +351,14 @@ impl fmt::Display for FieldName {
 }
 
 impl FieldName {
-    // Used in `field!` macro.
+    /// Construct a `FieldName`.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// Construct a `FieldName` in a `const` context.
+    ///
+    /// Prefer the [`field!`](crate::field) macro.
     #[doc(hidden)]
     pub const fn new_unchecked(name: &'static str) -> Self {
         Self(Cow::Borrowed(name))
@@ -171,19 +379,18 @@ impl AsRef<str> for FieldName {
 impl FromStr for FieldName {
     type Err = Error;
 
+    /// Never fails as any string is a valid name.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        validate_str_as_field_name(s)?;
-
-        Ok(Self(Cow::Owned(s.to_string())))
+        Ok(Self::new(s.to_string()))
     }
 }
 
 impl TryFrom<String> for FieldName {
     type Error = Error;
 
+    /// Never fails as any string is a valid name.
     fn try_from(name: String) -> Result<Self, Self::Error> {
-        validate_str_as_field_name(&name)?;
-        Ok(Self(Cow::Owned(name)))
+        Ok(Self::new(name))
     }
 }
 
@@ -195,12 +402,403 @@ impl<'a> TryFrom<&'a str> for FieldName {
     }
 }
 
-fn validate_str_as_field_name(name: &str) -> Result<(), Error> {
-    if name.contains(INVALID_FIELD_NAME_CHARS) {
-        Err(Error::InvalidCharInName(name.to_string()))
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FieldName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Split a path at all `.` that are not part of a quoted name or key.
+fn split_segments(path: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (idx, c) in path.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '.' if !quoted => {
+                segments.push(&path[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&path[start..]);
+    segments
+}
+
+/// Write `/` followed by the escaped token.
+fn write_json_pointer_token(f: &mut fmt::Formatter<'_>, token: &str) -> fmt::Result {
+    f.write_char('/')?;
+    for c in token.chars() {
+        match c {
+            '~' => f.write_str("~0")?,
+            '/' => f.write_str("~1")?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+/// Remove the escapes of a JSON Pointer's token.
+fn unescape_json_pointer_token(token: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next()? {
+                '0' => unescaped.push('~'),
+                '1' => unescaped.push('/'),
+                _ => return None,
+            },
+            c => unescaped.push(c),
+        }
+    }
+    Some(unescaped)
+}
+
+/// A bracketed part of a segment.
+enum Subscript {
+    Index(usize),
+    Key(String),
+}
+
+/// Parse the segments between two `.` of a path, e.g. `matrix[1][2]` into an
+/// array segment followed by an index segment.
+fn parse_segments(s: &str, segments: &mut Vec<PathSegment>) -> Result<(), Error> {
+    let (name, mut rest) = split_name(s)?;
+    if rest.is_empty() {
+        segments.push(PathSegment::Field(name));
+        return Ok(());
+    }
+
+    // Subscripts without a name belong to an unnamed array.
+    let mut name = (!s.starts_with('[')).then_some(name);
+    while !rest.is_empty() {
+        let (subscript, tail) = split_subscript(s, rest)?;
+        rest = tail;
+        segments.push(match (name.take(), subscript) {
+            (Some(name), Subscript::Index(index)) => PathSegment::Array { name, index },
+            (Some(name), Subscript::Key(key)) => PathSegment::Key { name, key },
+            (None, Subscript::Index(index)) => PathSegment::Index(index),
+            (None, Subscript::Key(_)) => return Err(Error::InvalidKeySegment(s.to_string())),
+        });
+    }
+    Ok(())
+}
+
+/// Split the leading `[index]` or `["key"]` from the rest of a segment.
+fn split_subscript<'a>(segment: &str, rest: &'a str) -> Result<(Subscript, &'a str), Error> {
+    let Some(inner) = rest.strip_prefix('[') else {
+        return Err(Error::InvalidCharInName(segment.to_string()));
+    };
+
+    if inner.starts_with('"') {
+        let invalid = || Error::InvalidKeySegment(segment.to_string());
+        let end = closing_quote(inner).ok_or_else(invalid)?;
+        let key = unquote(&inner[..=end]).ok_or_else(invalid)?;
+        let tail = inner[end + 1..].strip_prefix(']').ok_or_else(invalid)?;
+        return Ok((Subscript::Key(key), tail));
+    }
+
+    let end = inner
+        .find(']')
+        .ok_or_else(|| Error::IncompleteArraySegment(segment.to_string()))?;
+    Ok((Subscript::Index(inner[..end].parse()?), &inner[end + 1..]))
+}
+
+/// Split a segment into its leading, possibly quoted, name and the rest.
+fn split_name(segment: &str) -> Result<(FieldName, &str), Error> {
+    let invalid = || Error::InvalidCharInName(segment.to_string());
+
+    if segment.starts_with('"') {
+        let end = closing_quote(segment).ok_or_else(invalid)?;
+        let name = unquote(&segment[..=end]).ok_or_else(invalid)?;
+        return Ok((FieldName::new(name), &segment[end + 1..]));
+    }
+
+    let end = segment.find('[').unwrap_or(segment.len());
+    let name = &segment[..end];
+    if name.contains(QUOTED_FIELD_NAME_CHARS) {
+        return Err(if segment.ends_with(']') {
+            Error::IncompleteArraySegment(segment.to_string())
+        } else {
+            invalid()
+        });
+    }
+    Ok((FieldName::new(name.to_string()), &segment[end..]))
+}
+
+/// Find the index of the quote closing the quote at the start of `quoted`.
+fn closing_quote(quoted: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (idx, c) in quoted.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Write a name of a segment, quoted if necessary.
+pub(crate) fn write_name(f: &mut fmt::Formatter<'_>, name: &FieldName) -> fmt::Result {
+    if name.as_str().is_empty() || name.as_str().contains(QUOTED_FIELD_NAME_CHARS) {
+        write_quoted(f, name.as_str())
     } else {
-        Ok(())
+        f.write_str(name.as_str())
+    }
+}
+
+/// Write a string in quotes with `"` and `\` escaped.
+pub(crate) fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+/// Remove the quotes and escapes of a name or key.
+fn unquote(quoted: &str) -> Option<String> {
+    let inner = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    let mut key = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                escaped @ ('"' | '\\') => key.push(escaped),
+                _ => return None,
+            },
+            '"' => return None,
+            c => key.push(c),
+        }
+    }
+    Some(key)
+}
+
+/// Construct a [`FieldName`] constant.
+///
+/// ```
+/// use error_accumulator::{field, path::FieldName};
+///
+/// const URL: FieldName = field!("url");
+/// assert_eq!(URL.as_str(), "url");
+/// ```
+#[macro_export]
+macro_rules! field {
+    ($name:literal) => {{
+        const NAME: $crate::path::FieldName = $crate::path::FieldName::new_unchecked($name);
+        NAME
+    }};
+}
+
+/// Construct a [`SourcePath`] from its textual representation that is
+/// validated at compile time.
+///
+/// ```
+/// use error_accumulator::{
+///     field,
+///     path,
+///     path::{PathSegment, SourcePath},
+/// };
+///
+/// let path = path!(r#"foo.bar[3]."baz.qux""#);
+/// let expected = SourcePath::new()
+///     .join(PathSegment::field(field!("foo")))
+///     .join(PathSegment::array(field!("bar"), 3))
+///     .join(PathSegment::field(field!("baz.qux")));
+/// assert_eq!(path, expected);
+/// ```
+///
+/// Invalid paths fail to compile:
+///
+/// ```compile_fail
+/// # use error_accumulator::path;
+/// let path = path!("foo[bar]");
+/// ```
+#[macro_export]
+macro_rules! path {
+    ($path:literal) => {{
+        const _: () = ::core::assert!(
+            $crate::path::is_valid_source_path($path),
+            ::core::concat!("invalid source path: ", $path),
+        );
+        <$crate::path::SourcePath as ::core::str::FromStr>::from_str($path)
+            .expect("path was validated at compile time")
+    }};
+}
+
+// Used in `path!` macro. Must match the `FromStr` implementation of
+// `SourcePath`.
+#[doc(hidden)]
+pub const fn is_valid_source_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let mut start = 0;
+    let mut end = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    while end <= bytes.len() {
+        if end == bytes.len() || (bytes[end] == b'.' && !quoted) {
+            if !is_valid_segment(bytes, start, end) {
+                return false;
+            }
+            start = end + 1;
+        } else if escaped {
+            escaped = false;
+        } else if bytes[end] == b'\\' && quoted {
+            escaped = true;
+        } else if bytes[end] == b'"' {
+            quoted = !quoted;
+        }
+        end += 1;
+    }
+    true
+}
+
+const fn is_valid_segment(bytes: &[u8], start: usize, end: usize) -> bool {
+    let unnamed = start < end && bytes[start] == b'[';
+    let mut idx = if start < end && bytes[start] == b'"' {
+        match closing_quote_idx(bytes, start, end) {
+            Some(close) if is_valid_quoted(bytes, start, close + 1) => close + 1,
+            _ => return false,
+        }
+    } else {
+        match bare_name_end(bytes, start, end) {
+            Some(name_end) => name_end,
+            None => return false,
+        }
+    };
+
+    let mut is_first = true;
+    while idx < end {
+        if bytes[idx] != b'[' {
+            return false;
+        }
+        if idx + 1 < end && bytes[idx + 1] == b'"' {
+            // Only named segments may have a key.
+            if unnamed || !is_first {
+                return false;
+            }
+            let close = match closing_quote_idx(bytes, idx + 1, end) {
+                Some(close) if is_valid_quoted(bytes, idx + 1, close + 1) => close,
+                _ => return false,
+            };
+            if close + 1 == end || bytes[close + 1] != b']' {
+                return false;
+            }
+            idx = close + 2;
+        } else {
+            let mut close = idx + 1;
+            while close < end && bytes[close] != b']' {
+                close += 1;
+            }
+            if close == end || !is_valid_index(bytes, idx + 1, close) {
+                return false;
+            }
+            idx = close + 1;
+        }
+        is_first = false;
+    }
+    true
+}
+
+const fn closing_quote_idx(bytes: &[u8], start: usize, end: usize) -> Option<usize> {
+    let mut escaped = false;
+    let mut idx = start + 1;
+    while idx < end {
+        if escaped {
+            escaped = false;
+        } else if bytes[idx] == b'\\' {
+            escaped = true;
+        } else if bytes[idx] == b'"' {
+            return Some(idx);
+        }
+        idx += 1;
     }
+    None
+}
+
+/// The end of an unquoted name or `None` if it contains chars that require
+/// quoting.
+const fn bare_name_end(bytes: &[u8], start: usize, end: usize) -> Option<usize> {
+    let mut idx = start;
+    while idx < end && bytes[idx] != b'[' {
+        // All chars that require quoting are ASCII so checking the bytes is
+        // sufficient.
+        if matches!(bytes[idx], b'.' | b']' | b'"' | b'\\') {
+            return None;
+        }
+        idx += 1;
+    }
+    Some(idx)
+}
+
+const fn is_valid_quoted(bytes: &[u8], start: usize, end: usize) -> bool {
+    if end < start + 2 || bytes[start] != b'"' || bytes[end - 1] != b'"' {
+        return false;
+    }
+
+    let mut idx = start + 1;
+    while idx < end - 1 {
+        match bytes[idx] {
+            b'\\' => {
+                if idx + 1 == end - 1 || !matches!(bytes[idx + 1], b'"' | b'\\') {
+                    return false;
+                }
+                idx += 1;
+            }
+            b'"' => return false,
+            _ => {}
+        }
+        idx += 1;
+    }
+    true
+}
+
+const fn is_valid_index(bytes: &[u8], start: usize, end: usize) -> bool {
+    if start >= end {
+        return false;
+    }
+
+    let mut index: usize = 0;
+    let mut idx = start;
+    while idx < end {
+        if !bytes[idx].is_ascii_digit() {
+            return false;
+        }
+        index = match index.checked_mul(10) {
+            Some(index) => match index.checked_add((bytes[idx] - b'0') as usize) {
+                Some(index) => index,
+                None => return false,
+            },
+            None => return false,
+        };
+        idx += 1;
+    }
+    true
 }
 
 #[cfg(test)]
@@ -241,4 +839,220 @@ mod tests {
 
         assert_eq!(parsed, expect);
     }
+
+    #[test]
+    fn should_round_trip_index_segments() {
+        let path = SourcePath::new()
+            .join(PathSegment::index(0))
+            .join(PathSegment::array(n("matrix"), 1))
+            .join(PathSegment::index(2))
+            .join(PathSegment::index(3))
+            .join(PathSegment::field(n("foo")));
+
+        let string = path.to_string();
+
+        assert_eq!(string.as_str(), "[0].matrix[1][2][3].foo");
+        assert_eq!(string.parse::<SourcePath>().unwrap(), path);
+        assert_eq!(path.to_json_pointer(), "/0/matrix/1/2/3/foo");
+        assert_eq!(
+            SourcePath::from_json_pointer("/0/matrix/1/2/3/foo").unwrap(),
+            path
+        );
+        assert!(matches!(
+            "matrix[1][2]".parse::<PathSegment>(),
+            Err(Error::MultipleSegments(_))
+        ));
+    }
+
+    #[test]
+    fn should_compose_paths() {
+        let mut path: SourcePath = "foo.bar".parse().unwrap();
+        let rest: SourcePath = "baz[1].qux".parse().unwrap();
+
+        let full = path.concat(&rest);
+        assert_eq!(full, "foo.bar.baz[1].qux".parse().unwrap());
+        assert_eq!(full.depth(), 4);
+        assert_eq!(full.strip_prefix(&path), Some(rest.clone()));
+        assert_eq!(rest.strip_prefix(&path), None);
+        assert_eq!(
+            full.parent().unwrap().last(),
+            Some(&PathSegment::array(n("baz"), 1))
+        );
+        assert_eq!(SourcePath::new().parent(), None);
+
+        path.push(PathSegment::field(n("baz")));
+        assert_eq!(path.pop(), Some(PathSegment::field(n("baz"))));
+        assert_eq!(
+            path.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["foo", "bar"]
+        );
+    }
+
+    #[test]
+    fn should_convert_json_pointer() {
+        let path = SourcePath::new()
+            .join(PathSegment::field(n("a/b")))
+            .join(PathSegment::array(n("c~d"), 42))
+            .join(PathSegment::key(n("e"), "f/~g"));
+
+        let pointer = path.to_json_pointer();
+
+        assert_eq!(pointer, "/a~1b/c~0d/42/e/f~1~0g");
+        assert_eq!(
+            SourcePath::from_json_pointer(&pointer).unwrap(),
+            "a/b.c~d[42].e.f/~g".parse().unwrap()
+        );
+        assert_eq!(SourcePath::new().to_json_pointer(), "");
+        assert_eq!(
+            SourcePath::from_json_pointer("").unwrap(),
+            SourcePath::new()
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_json_pointer() {
+        for pointer in ["foo", "/foo~2", "/foo~"] {
+            assert!(SourcePath::from_json_pointer(pointer).is_err(), "{pointer}");
+        }
+    }
+
+    #[test]
+    fn should_match_base() {
+        let path: SourcePath = "foo.bar.baz".parse().unwrap();
+
+        assert!(path.is_matching_base(&SourcePath::new()));
+        assert!(path.is_matching_base(&"foo.bar".parse().unwrap()));
+        assert!(path.is_matching_base(&path));
+        assert!(!path.is_matching_base(&"foo.baz".parse().unwrap()));
+        assert!(!path.is_matching_base(&"foo.bar.baz.qux".parse().unwrap()));
+    }
+
+    #[test]
+    fn should_round_trip_key_segments() {
+        let path = SourcePath::new()
+            .join(PathSegment::key(n("hosts"), "api"))
+            .join(PathSegment::key(
+                n("labels"),
+                "app.kubernetes.io/[\"name\"]\\",
+            ))
+            .join(PathSegment::field(n("url")));
+
+        let string = path.to_string();
+
+        assert_eq!(
+            string.as_str(),
+            r#"hosts["api"].labels["app.kubernetes.io/[\"name\"]\\"].url"#
+        );
+        assert_eq!(string.parse::<SourcePath>().unwrap(), path);
+    }
+
+    #[test]
+    fn should_round_trip_quoted_field_names() {
+        let path = SourcePath::new()
+            .join(PathSegment::field(FieldName::new("example.com")))
+            .join(PathSegment::array(FieldName::new("a[0]"), 1))
+            .join(PathSegment::key(
+                FieldName::new(r#"quote"and\backslash"#),
+                "app.kubernetes.io/name",
+            ))
+            .join(PathSegment::field(FieldName::new("")));
+
+        let string = path.to_string();
+
+        assert_eq!(
+            string.as_str(),
+            r#""example.com"."a[0]"[1]."quote\"and\\backslash"["app.kubernetes.io/name"]."""#
+        );
+        assert_eq!(string.parse::<SourcePath>().unwrap(), path);
+    }
+
+    #[test]
+    fn should_reject_unquoted_special_chars() {
+        for path in [r#"foo"bar"#, r#"foo\bar"#, r#""foo"bar"#, r#""foo"#] {
+            assert!(
+                matches!(path.parse::<SourcePath>(), Err(Error::InvalidCharInName(_))),
+                "{path}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_round_trip_serde() {
+        let path = SourcePath::new()
+            .join(PathSegment::key(n("hosts"), "api"))
+            .join(PathSegment::array(n("ports"), 2))
+            .join(PathSegment::field(n("url")));
+
+        let json = serde_json::to_string(&path).unwrap();
+
+        assert_eq!(serde_json::from_str::<SourcePath>(&json).unwrap(), path);
+        assert_eq!(
+            serde_json::from_str::<FieldName>(r#""foo.bar""#).unwrap(),
+            n("foo.bar")
+        );
+    }
+
+    #[test]
+    fn should_construct_const_field_name() {
+        const FOO: FieldName = crate::field!("foo");
+
+        assert_eq!(FOO, n("foo"));
+    }
+
+    #[test]
+    fn should_construct_path_with_macro() {
+        let expect = SourcePath::new()
+            .join(PathSegment::array(n("foo"), 21))
+            .join(PathSegment::field(n("bar")));
+
+        assert_eq!(crate::path!("foo[21].bar"), expect);
+    }
+
+    #[test]
+    fn should_validate_like_from_str() {
+        for path in [
+            "foo",
+            "foo.bar[42].baz",
+            "foo[]",
+            "foo[a]",
+            "foo]",
+            "foo[1",
+            "foo[1]bar",
+            "foo[99999999999999999999999]",
+            "a..b",
+            "foo[\"bar\"]",
+            "foo[\"a.b[0]\"].c",
+            "foo[\"a\\\"b\"]",
+            "foo[\"a\"b\"]",
+            "foo[\"a\\\"]",
+            "foo[\"]",
+            "foo[\"a\\b\"]",
+            "foo[\"\"]",
+            "\"foo.bar\"",
+            "\"foo.bar\"[1].\"baz\"[\"qux\"]",
+            "\"foo\\\"\"",
+            "\"foo\"bar",
+            "\"foo",
+            "foo\"bar",
+            "foo\\bar",
+            "\"\"",
+            "\"a\\b\"",
+            "[0]",
+            "[0][1].foo",
+            "foo[0][1]",
+            "foo[\"a\"][1]",
+            "foo[0][\"a\"]",
+            "[\"a\"]",
+            "foo[0]]",
+            "foo[0][",
+            "[0]foo",
+        ] {
+            assert_eq!(
+                is_valid_source_path(path),
+                path.parse::<SourcePath>().is_ok(),
+                "{path}"
+            );
+        }
+    }
 }
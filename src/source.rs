@@ -0,0 +1,207 @@
+//! [`SourceMap`] to locate the source of an accumulated error in the original
+//! document.
+
+use std::{collections::HashMap, fmt, ops::Range};
+
+use crate::path::SourcePath;
+
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod json;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[cfg(feature = "miette")]
+pub use self::diagnostic::Diagnostic;
+
+/// Errors building a [`SourceMap`] from a document.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Invalid JSON document.
+    #[error("invalid JSON at byte {offset}: {message}")]
+    Json {
+        /// The byte offset of the error in the document.
+        offset: usize,
+        /// What is wrong.
+        message: &'static str,
+    },
+    /// Invalid YAML document.
+    #[cfg(feature = "yaml")]
+    #[error("invalid YAML")]
+    Yaml(#[from] yaml_rust2::ScanError),
+    /// Invalid TOML document.
+    #[cfg(feature = "toml")]
+    #[error("invalid TOML")]
+    Toml(#[from] toml_edit::TomlError),
+}
+
+/// Associates [`SourcePath`]s with their positions in a document.
+///
+/// Paths are matched by their [JSON Pointer](SourcePath::to_json_pointer), so
+/// `hosts["api"]` and `hosts.api` refer to the same position. If a path is not
+/// part of the document, e.g. because it is a missing field, the position of
+/// its closest ancestor is used.
+///
+/// ```
+/// use error_accumulator::{path, source::SourceMap};
+///
+/// let source = "{\n  \"hosts\": [\n    { \"url\": \"/api\" }\n  ]\n}";
+/// let map = SourceMap::from_json("config.json", source).unwrap();
+///
+/// let location = map.location(&path!("hosts[0].url")).unwrap();
+/// assert_eq!((location.line, location.column), (3, 14));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    name: String,
+    source: String,
+    line_starts: Vec<usize>,
+    spans: HashMap<String, Range<usize>>,
+}
+
+/// A position in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    /// The line, starting at 1.
+    pub line: usize,
+    /// The column in characters, starting at 1.
+    pub column: usize,
+}
+
+impl SourceMap {
+    /// Construct an empty map for the document `source` with the given name,
+    /// e.g. its file name.
+    ///
+    /// Use [`insert()`](Self::insert) to add positions of paths.
+    pub fn new(name: impl Into<String>, source: impl Into<String>) -> Self {
+        let source = source.into();
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Self {
+            name: name.into(),
+            source,
+            line_starts,
+            spans: HashMap::new(),
+        }
+    }
+
+    /// Build the map of a JSON document.
+    ///
+    /// Documents with more than 128 nested arrays and objects are rejected.
+    pub fn from_json(name: impl Into<String>, source: impl Into<String>) -> Result<Self, Error> {
+        let mut map = Self::new(name, source);
+        let spans = json::spans(&map.source)?;
+        for (path, span) in spans {
+            map.insert(&path, span);
+        }
+        Ok(map)
+    }
+
+    /// Build the map of a YAML document.
+    ///
+    /// Spans of scalars with escape sequences or multiple lines are
+    /// approximated.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(name: impl Into<String>, source: impl Into<String>) -> Result<Self, Error> {
+        let mut map = Self::new(name, source);
+        let spans = yaml::spans(&map.source)?;
+        for (path, span) in spans {
+            map.insert(&path, span);
+        }
+        Ok(map)
+    }
+
+    /// Build the map of a TOML document.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(name: impl Into<String>, source: impl Into<String>) -> Result<Self, Error> {
+        let mut map = Self::new(name, source);
+        let spans = toml::spans(&map.source)?;
+        for (path, span) in spans {
+            map.insert(&path, span);
+        }
+        Ok(map)
+    }
+
+    /// The name of the document.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Associate a path with the byte range of its value in the document.
+    pub fn insert(&mut self, path: &SourcePath, span: Range<usize>) {
+        self.spans.insert(path.to_json_pointer(), span);
+    }
+
+    /// The byte range of the path's value or of its closest ancestor in the
+    /// document.
+    pub fn span(&self, path: &SourcePath) -> Option<Range<usize>> {
+        let mut path = path.clone();
+        loop {
+            if let Some(span) = self.spans.get(&path.to_json_pointer()) {
+                return Some(span.clone());
+            }
+            path.pop()?;
+        }
+    }
+
+    /// The start of the path's value or of its closest ancestor in the
+    /// document.
+    pub fn location(&self, path: &SourcePath) -> Option<Location> {
+        self.span(path)
+            .map(|span| self.location_of_offset(span.start))
+    }
+
+    fn location_of_offset(&self, offset: usize) -> Location {
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= offset)
+            .max(1);
+        let line_start = self.line_starts[line - 1];
+        let column = self
+            .source
+            .get(line_start..offset)
+            .map_or(1, |prefix| prefix.chars().count() + 1);
+        Location { line, column }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_fall_back_to_ancestor() {
+        let map = SourceMap::from_json("test.json", "{\n\"a\": {\n\"b\": 1 } }").unwrap();
+
+        assert_eq!(
+            map.location(&"a.c".parse().unwrap()),
+            Some(Location { line: 2, column: 6 })
+        );
+        assert_eq!(
+            map.location(&SourcePath::new()),
+            Some(Location { line: 1, column: 1 })
+        );
+    }
+
+    #[test]
+    fn should_count_columns_in_chars() {
+        let map = SourceMap::from_json("test.json", r#"{ "ä": "ö", "b": 1 }"#).unwrap();
+
+        assert_eq!(
+            map.location(&"b".parse().unwrap()),
+            Some(Location {
+                line: 1,
+                column: 18
+            })
+        );
+    }
+}
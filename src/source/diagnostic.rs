@@ -0,0 +1,189 @@
+//! Rich diagnostics with [`miette`].
+
+use std::{error::Error, fmt};
+
+use miette::{LabeledSpan, MietteError, MietteSpanContents, SourceCode, SourceSpan, SpanContents};
+
+use super::SourceMap;
+use crate::{error::AccumulatedError, path::SourcePath};
+
+type ErrorRef<'a> = &'a (dyn Error + Send + Sync + 'static);
+
+/// A [`miette::Diagnostic`] labeling every error of an [`AccumulatedError`]
+/// in the original document.
+///
+/// Errors of documents that are mounted at a path of the input, e.g. an
+/// included file, can be grouped into related diagnostics with
+/// [`with_file()`](Self::with_file).
+///
+/// Render it with a [`miette::ReportHandler`], e.g. by converting it into a
+/// [`miette::Report`] with miette's `fancy` feature enabled, to get colored
+/// code snippets.
+///
+/// ```
+/// use error_accumulator::{ErrorAccumulator, field, source::SourceMap};
+///
+/// let source = SourceMap::from_json("config.json", r#"{ "port": "http" }"#).unwrap();
+/// let errors = ErrorAccumulator::new()
+///     .field(field!("port"), "http".parse::<u16>())
+///     .analyse()
+///     .unwrap_err();
+///
+/// let diagnostic = errors.diagnostic(&source);
+/// assert_eq!(diagnostic.to_string(), "found 1 error in config.json");
+/// ```
+#[derive(Debug)]
+pub struct Diagnostic<'a> {
+    source: &'a SourceMap,
+    base: SourcePath,
+    errors: Vec<(&'a SourcePath, ErrorRef<'a>)>,
+    related: Vec<Diagnostic<'a>>,
+}
+
+impl<'a> Diagnostic<'a> {
+    /// Construct the diagnostic of all errors located in `source`.
+    pub fn new(errors: &'a AccumulatedError, source: &'a SourceMap) -> Self {
+        Self {
+            source,
+            base: SourcePath::new(),
+            errors: errors.iter().collect(),
+            related: Vec::new(),
+        }
+    }
+
+    /// Move the errors under `base` into a related diagnostic of the document
+    /// `source` whose root is found at `base`.
+    pub fn with_file(mut self, base: SourcePath, source: &'a SourceMap) -> Self {
+        let errors = self
+            .errors
+            .extract_if(.., |(path, _)| path.starts_with(&base))
+            .collect();
+        self.related.push(Self {
+            source,
+            base,
+            errors,
+            related: Vec::new(),
+        });
+        self
+    }
+
+    fn span(&self, path: &SourcePath) -> Option<SourceSpan> {
+        let relative = path.strip_prefix(&self.base)?;
+        self.source.span(&relative).map(SourceSpan::from)
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.errors.len();
+        let plural = if count == 1 { "" } else { "s" };
+        write!(f, "found {count} error{plural} in {}", self.source.name())
+    }
+}
+
+impl Error for Diagnostic<'_> {}
+
+impl miette::Diagnostic for Diagnostic<'_> {
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let unlocated = self
+            .errors
+            .iter()
+            .filter(|(path, _)| self.span(path).is_none())
+            .map(|(path, error)| format!("{path}: {error}"))
+            .collect::<Vec<_>>();
+        (!unlocated.is_empty()).then(|| Box::new(unlocated.join("\n")) as Box<dyn fmt::Display>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.errors.iter().filter_map(|(path, error)| {
+            let span = self.span(path)?;
+            Some(LabeledSpan::new_with_span(
+                Some(format!("{path}: {error}")),
+                span,
+            ))
+        })))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn miette::Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.related
+                .iter()
+                .map(|diagnostic| diagnostic as &dyn miette::Diagnostic),
+        ))
+    }
+}
+
+impl SourceCode for SourceMap {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        let contents = self
+            .source
+            .read_span(span, context_lines_before, context_lines_after)?;
+        Ok(Box::new(MietteSpanContents::new_named(
+            self.name.clone(),
+            contents.data(),
+            *contents.span(),
+            contents.line(),
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::{Diagnostic as _, GraphicalReportHandler, GraphicalTheme};
+
+    use super::*;
+    use crate::error::MissingField;
+
+    #[test]
+    fn should_label_errors() {
+        let source = SourceMap::from_json("config.json", "{\n  \"port\": \"http\"\n}").unwrap();
+        let mut errors = AccumulatedError::default();
+        errors.push("port".parse().unwrap(), MissingField);
+
+        let diagnostic = errors.diagnostic(&source);
+        let mut rendered = String::new();
+        GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+            .render_report(&mut rendered, &diagnostic)
+            .unwrap();
+
+        assert!(rendered.contains("[config.json:2:11]"), "{rendered}");
+        assert!(
+            rendered.contains("port: required field is missing"),
+            "{rendered}"
+        );
+    }
+
+    #[test]
+    fn should_group_errors_by_file() {
+        let main = SourceMap::from_json("main.json", r#"{ "name": 1 }"#).unwrap();
+        let db = SourceMap::from_json("db.json", r#"{ "url": 1 }"#).unwrap();
+        let mut errors = AccumulatedError::default();
+        errors.push("name".parse().unwrap(), MissingField);
+        errors.push("db.url".parse().unwrap(), MissingField);
+        errors.push("other".parse().unwrap(), MissingField);
+
+        let diagnostic = errors
+            .diagnostic(&main)
+            .with_file("db".parse().unwrap(), &db);
+
+        assert_eq!(diagnostic.to_string(), "found 2 errors in main.json");
+        assert_eq!(diagnostic.labels().unwrap().count(), 2);
+        let related = diagnostic.related().unwrap().collect::<Vec<_>>();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].to_string(), "found 1 error in db.json");
+        let label = related[0].labels().unwrap().next().unwrap();
+        assert_eq!(label.offset(), 9);
+        assert_eq!(label.label(), Some("db.url: required field is missing"));
+    }
+}
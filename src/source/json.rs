@@ -0,0 +1,332 @@
+//! Minimal JSON scanner recording the span of every value.
+//!
+//! Unlike YAML and TOML, JSON is not behind a feature because the scanner has
+//! no dependencies. Parsers like `serde_json` don't report the spans of values,
+//! so they would not help here anyway.
+
+use std::ops::Range;
+
+use super::Error;
+use crate::path::{FieldName, PathSegment, SourcePath};
+
+/// The maximum number of nested arrays and objects, the same as the one of
+/// `serde_json`.
+const MAX_DEPTH: usize = 128;
+
+/// Scan the JSON document and collect the spans of all values.
+///
+/// The document is validated except for control characters in strings and
+/// duplicate keys.
+pub(super) fn spans(source: &str) -> Result<Vec<(SourcePath, Range<usize>)>, Error> {
+    let mut scanner = Scanner {
+        source,
+        pos: 0,
+        depth: 0,
+        spans: Vec::new(),
+    };
+    scanner.value(&mut SourcePath::new())?;
+    scanner.skip_whitespace();
+    if scanner.pos != source.len() {
+        return Err(scanner.error("trailing characters"));
+    }
+    Ok(scanner.spans)
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    pos: usize,
+    depth: usize,
+    spans: Vec<(SourcePath, Range<usize>)>,
+}
+
+impl Scanner<'_> {
+    fn value(&mut self, path: &mut SourcePath) -> Result<(), Error> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => {
+                self.enter()?;
+                self.object(path)?;
+                self.depth -= 1;
+            }
+            Some(b'[') => {
+                self.enter()?;
+                self.array(path)?;
+                self.depth -= 1;
+            }
+            Some(b'"') => {
+                self.string()?;
+            }
+            Some(_) => self.literal()?,
+            None => return Err(self.error("unexpected end of input")),
+        }
+        self.spans.push((path.clone(), start..self.pos));
+        Ok(())
+    }
+
+    fn enter(&mut self) -> Result<(), Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn object(&mut self, path: &mut SourcePath) -> Result<(), Error> {
+        self.pos += 1;
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            path.push(PathSegment::field(FieldName::new(key)));
+            self.value(path)?;
+            path.pop();
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => {}
+                Some(b'}') => return Ok(()),
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self, path: &mut SourcePath) -> Result<(), Error> {
+        self.pos += 1;
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+        let mut index = 0;
+        loop {
+            path.push(PathSegment::index(index));
+            index += 1;
+            self.value(path)?;
+            path.pop();
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => {}
+                Some(b']') => return Ok(()),
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect(b'"')?;
+        let mut value = String::new();
+        loop {
+            let rest = &self.source[self.pos..];
+            let Some(end) = rest.find(['"', '\\']) else {
+                return Err(self.error("unterminated string"));
+            };
+            value.push_str(&rest[..end]);
+            self.pos += end + 1;
+            if rest.as_bytes()[end] == b'"' {
+                return Ok(value);
+            }
+            let escaped = match self.next() {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{8}',
+                Some(b'f') => '\u{c}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => self.unicode_escape()?,
+                _ => return Err(self.error("invalid escape sequence")),
+            };
+            value.push(escaped);
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.source[self.pos..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .source
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let code =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn literal(&mut self) -> Result<(), Error> {
+        let rest = &self.source[self.pos..];
+        if let Some(keyword) = ["true", "false", "null"]
+            .into_iter()
+            .find(|keyword| rest.starts_with(keyword))
+        {
+            self.pos += keyword.len();
+            return Ok(());
+        }
+        self.number()
+    }
+
+    fn number(&mut self) -> Result<(), Error> {
+        self.eat(b'-');
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => {
+                self.digits();
+            }
+            _ => return Err(self.error("expected a value")),
+        }
+        if self.eat(b'.') && self.digits() == 0 {
+            return Err(self.error("expected a digit"));
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            let _ = self.eat(b'+') || self.eat(b'-');
+            if self.digits() == 0 {
+                return Err(self.error("expected a digit"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip ASCII digits and return how many were skipped.
+    fn digits(&mut self) -> usize {
+        let rest = &self.source[self.pos..];
+        let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        self.pos += len;
+        len
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let is_next = self.peek() == Some(byte);
+        if is_next {
+            self.pos += 1;
+        }
+        is_next
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), Error> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(match byte {
+                b'"' => "expected a string",
+                b':' => "expected `:`",
+                _ => "unexpected character",
+            }))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\r', '\n']).len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn error(&self, message: &'static str) -> Error {
+        Error::Json {
+            offset: self.pos,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span_of<'a>(source: &'a str, path: &str) -> &'a str {
+        let path: SourcePath = path.parse().unwrap();
+        let (_, span) = spans(source)
+            .unwrap()
+            .into_iter()
+            .find(|(p, _)| p.to_json_pointer() == path.to_json_pointer())
+            .unwrap();
+        &source[span]
+    }
+
+    #[test]
+    fn should_record_spans_of_values() {
+        let source = r#"{ "hosts": [ { "url": "/api", "port": 80 } ], "a\"b": null }"#;
+
+        assert_eq!(span_of(source, "hosts[0].url"), r#""/api""#);
+        assert_eq!(span_of(source, "hosts[0].port"), "80");
+        assert_eq!(
+            span_of(source, "hosts[0]"),
+            r#"{ "url": "/api", "port": 80 }"#
+        );
+        assert_eq!(span_of(source, r#""a\"b""#), "null");
+    }
+
+    #[test]
+    fn should_reject_invalid_documents() {
+        for source in ["", "{", r#"{ "a" 1 }"#, "[1 2]", r#""\x""#, "1 2"] {
+            assert!(spans(source).is_err(), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn should_check_literals_and_numbers() {
+        assert!(spans("[true, false, null, 0, -1, 1.5, 10e3, -0.5E-2, 2e+1]").is_ok());
+
+        for source in [
+            r#"{ "a": nope }"#,
+            r#"{ "a": 01x }"#,
+            "[tru]",
+            "[nulls]",
+            "[01]",
+            "[-]",
+            "[+1]",
+            "[.5]",
+            "[1.]",
+            "[1e]",
+            "[0x10]",
+        ] {
+            assert!(spans(source).is_err(), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn should_limit_nesting() {
+        let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+
+        assert!(spans(&nested(MAX_DEPTH)).is_ok());
+        assert!(matches!(
+            spans(&nested(200_000)),
+            Err(Error::Json {
+                offset: MAX_DEPTH,
+                message: "nesting too deep",
+            })
+        ));
+    }
+}
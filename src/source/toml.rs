@@ -0,0 +1,114 @@
+//! Spans of values in a TOML document using [`toml_edit`].
+
+use std::ops::Range;
+
+use toml_edit::{Document, Item, Table, Value};
+
+use super::Error;
+use crate::path::{FieldName, PathSegment, SourcePath};
+
+/// Parse the TOML document and collect the spans of all values.
+///
+/// Implicit tables, e.g. `a` of `a.b = 1`, are located at their key.
+pub(super) fn spans(source: &str) -> Result<Vec<(SourcePath, Range<usize>)>, Error> {
+    let document = Document::parse(source)?;
+    let mut spans = vec![(SourcePath::new(), 0..source.len())];
+    table(document.as_table(), &mut SourcePath::new(), &mut spans);
+    Ok(spans)
+}
+
+fn table(table: &Table, path: &mut SourcePath, spans: &mut Vec<(SourcePath, Range<usize>)>) {
+    for (name, value) in table.iter() {
+        path.push(PathSegment::field(FieldName::new(name.to_string())));
+        let key_span = table.key(name).and_then(|key| key.span());
+        item(value, key_span, path, spans);
+        path.pop();
+    }
+}
+
+fn item(
+    item: &Item,
+    key_span: Option<Range<usize>>,
+    path: &mut SourcePath,
+    spans: &mut Vec<(SourcePath, Range<usize>)>,
+) {
+    if let Some(span) = item.span().or(key_span) {
+        spans.push((path.clone(), span));
+    }
+    match item {
+        Item::None => {}
+        Item::Value(value) => nested_value(value, path, spans),
+        Item::Table(t) => table(t, path, spans),
+        Item::ArrayOfTables(tables) => {
+            for (index, t) in tables.iter().enumerate() {
+                path.push(PathSegment::index(index));
+                if let Some(span) = t.span() {
+                    spans.push((path.clone(), span));
+                }
+                table(t, path, spans);
+                path.pop();
+            }
+        }
+    }
+}
+
+fn value(value: &Value, path: &mut SourcePath, spans: &mut Vec<(SourcePath, Range<usize>)>) {
+    if let Some(span) = value.span() {
+        spans.push((path.clone(), span));
+    }
+    nested_value(value, path, spans);
+}
+
+fn nested_value(value: &Value, path: &mut SourcePath, spans: &mut Vec<(SourcePath, Range<usize>)>) {
+    match value {
+        Value::Array(array) => {
+            for (index, element) in array.iter().enumerate() {
+                path.push(PathSegment::index(index));
+                self::value(element, path, spans);
+                path.pop();
+            }
+        }
+        Value::InlineTable(t) => {
+            for (name, element) in t.iter() {
+                path.push(PathSegment::field(FieldName::new(name.to_string())));
+                self::value(element, path, spans);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_spans_of_values() {
+        let source = "\
+name = \"test\"
+limits.max = 3
+
+[[hosts]]
+url = \"/api\"
+ports = [80, 443]
+tls = { cert = \"a.pem\" }
+";
+        let spans = spans(source).unwrap();
+        let span_of = |path: &str| {
+            let path: SourcePath = path.parse().unwrap();
+            let (_, span) = spans
+                .iter()
+                .find(|(p, _)| p.to_json_pointer() == path.to_json_pointer())
+                .unwrap();
+            &source[span.clone()]
+        };
+
+        assert_eq!(span_of("name"), "\"test\"");
+        assert_eq!(span_of("limits"), "limits");
+        assert_eq!(span_of("limits.max"), "3");
+        assert_eq!(span_of("hosts[0].url"), "\"/api\"");
+        assert_eq!(span_of("hosts[0].ports[1]"), "443");
+        assert_eq!(span_of("hosts[0].tls.cert"), "\"a.pem\"");
+    }
+}
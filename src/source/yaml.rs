@@ -0,0 +1,211 @@
+//! Spans of values in a YAML document using [`yaml_rust2`].
+
+use std::{mem, ops::Range};
+
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::{Marker, TScalarStyle},
+};
+
+use super::Error;
+use crate::path::{FieldName, PathSegment, SourcePath};
+
+/// Parse the first document of the YAML stream and collect the spans of all
+/// values.
+///
+/// Values of mappings with non-scalar keys are not recorded.
+pub(super) fn spans(source: &str) -> Result<Vec<(SourcePath, Range<usize>)>, Error> {
+    let mut receiver = Receiver {
+        char_offsets: source
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(source.len()))
+            .collect(),
+        path: SourcePath::new(),
+        frames: Vec::new(),
+        skipped: 0,
+        spans: Vec::new(),
+    };
+    Parser::new_from_str(source).load(&mut receiver, false)?;
+    Ok(receiver.spans)
+}
+
+struct Receiver {
+    /// The byte offset of every char as [`Marker`]s count chars.
+    char_offsets: Vec<usize>,
+    path: SourcePath,
+    frames: Vec<Frame>,
+    /// Depth of the collection currently skipped.
+    skipped: usize,
+    spans: Vec<(SourcePath, Range<usize>)>,
+}
+
+enum Frame {
+    Sequence { start: usize, index: usize },
+    Mapping { start: usize, key: Key },
+}
+
+enum Key {
+    Expected,
+    Scalar(String),
+    Complex,
+    Value,
+    Skipped,
+}
+
+impl Receiver {
+    fn offset(&self, mark: Marker) -> usize {
+        self.char_offsets
+            .get(mark.index())
+            .or(self.char_offsets.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Enter the next value of the current collection.
+    ///
+    /// Returns whether the value is recorded, i.e. it is neither a key nor the
+    /// value of a non-scalar key.
+    fn start_value(&mut self, scalar: Option<String>) -> bool {
+        match self.frames.last_mut() {
+            None => true,
+            Some(Frame::Sequence { index, .. }) => {
+                self.path.push(PathSegment::index(*index));
+                *index += 1;
+                true
+            }
+            Some(Frame::Mapping { key, .. }) => match mem::replace(key, Key::Expected) {
+                Key::Expected => {
+                    *key = scalar.map_or(Key::Complex, Key::Scalar);
+                    false
+                }
+                Key::Scalar(name) => {
+                    self.path.push(PathSegment::field(FieldName::new(name)));
+                    *key = Key::Value;
+                    true
+                }
+                Key::Complex | Key::Value | Key::Skipped => {
+                    *key = Key::Skipped;
+                    false
+                }
+            },
+        }
+    }
+
+    /// Leave the value entered by [`start_value()`](Self::start_value).
+    fn end_value(&mut self) {
+        match self.frames.last_mut() {
+            None => {}
+            Some(Frame::Sequence { .. }) => {
+                self.path.pop();
+            }
+            Some(Frame::Mapping { key, .. }) => match key {
+                Key::Expected | Key::Scalar(_) | Key::Complex => {}
+                Key::Value => {
+                    self.path.pop();
+                    *key = Key::Expected;
+                }
+                Key::Skipped => *key = Key::Expected,
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for Receiver {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let offset = self.offset(mark);
+        if self.skipped > 0 {
+            match event {
+                Event::SequenceStart(..) | Event::MappingStart(..) => self.skipped += 1,
+                Event::SequenceEnd | Event::MappingEnd => {
+                    self.skipped -= 1;
+                    if self.skipped == 0 {
+                        self.end_value();
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+        match event {
+            Event::Scalar(value, style, ..) => {
+                let len = match style {
+                    TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted => value.len() + 2,
+                    _ => value.len(),
+                };
+                if self.start_value(Some(value)) {
+                    let end = (offset + len).min(*self.char_offsets.last().unwrap_or(&offset));
+                    self.spans.push((self.path.clone(), offset..end));
+                }
+                self.end_value();
+            }
+            Event::Alias(_) => {
+                if self.start_value(None) {
+                    self.spans.push((self.path.clone(), offset..offset));
+                }
+                self.end_value();
+            }
+            Event::SequenceStart(..) => {
+                if self.start_value(None) {
+                    self.frames.push(Frame::Sequence {
+                        start: offset,
+                        index: 0,
+                    });
+                } else {
+                    self.skipped = 1;
+                }
+            }
+            Event::MappingStart(..) => {
+                if self.start_value(None) {
+                    self.frames.push(Frame::Mapping {
+                        start: offset,
+                        key: Key::Expected,
+                    });
+                } else {
+                    self.skipped = 1;
+                }
+            }
+            Event::SequenceEnd | Event::MappingEnd => {
+                if let Some(Frame::Sequence { start, .. } | Frame::Mapping { start, .. }) =
+                    self.frames.pop()
+                {
+                    self.spans
+                        .push((self.path.clone(), start..offset.max(start)));
+                }
+                self.end_value();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_spans_of_values() {
+        let source = "\
+hosts:
+  - url: \"/ä\"
+    port: 80
+? [complex]
+: ignored
+name: test
+";
+        let spans = spans(source).unwrap();
+        let span_of = |path: &str| {
+            let path: SourcePath = path.parse().unwrap();
+            let (_, span) = spans
+                .iter()
+                .find(|(p, _)| p.to_json_pointer() == path.to_json_pointer())
+                .unwrap();
+            &source[span.clone()]
+        };
+
+        assert_eq!(span_of("hosts[0].url"), "\"/ä\"");
+        assert_eq!(span_of("hosts[0].port"), "80");
+        assert_eq!(span_of("name"), "test");
+        assert_eq!(spans.len(), 6);
+    }
+}
@@ -0,0 +1,719 @@
+//! Common validation functions and composable [`Validator`]s.
+//!
+//! Each function returns its input if it is valid so it can be recorded with
+//! [`FieldBuilder::localized_value()`](crate::builder::FieldBuilder::localized_value).
+//! Further checks of the same field can be chained with
+//! [`FieldBuilder::check_localized()`](crate::builder::FieldBuilder::check_localized)
+//! which records every failing check, not only the first.
+//!
+//! The errors have [codes](ErrorCode) and [message keys](Localize), e.g.
+//! `out_of_range`, that these methods attach, as do the [`Validator`]s like
+//! [`InRange`].
+//!
+//! ```
+//! use error_accumulator::{ErrorAccumulator, field, validators};
+//!
+//! let name = "";
+//! let errors = ErrorAccumulator::new()
+//!     .field_builder(field!("name"))
+//!     .localized_value(validators::not_empty(name))
+//!     .check_localized(validators::length_between(name, 3, 16))
+//!     .check_localized(validators::one_of(name, &["api", "web"]))
+//!     .on_ok(|name: &str| name.to_string())
+//!     .finish()
+//!     .analyse()
+//!     .unwrap_err();
+//!
+//! assert_eq!(errors.len(), 3);
+//! assert_eq!(errors.get_by_code("empty").count(), 1);
+//! assert_eq!(errors.get_by_code("too_short").count(), 1);
+//! ```
+//!
+//! Rules that are shared by many inputs can be defined once as [`Validator`]s
+//! and composed with combinators like [`And`] or [`Each`].
+
+#[cfg(feature = "regex")]
+use std::sync::LazyLock;
+use std::{borrow::Cow, error::Error, fmt, ops::RangeInclusive};
+
+use crate::{
+    error::{AccumulatedError, Coded, ErrorCode, Localize, MessageArg, MessageArgs},
+    path::SourcePath,
+};
+
+mod combinators;
+
+pub use self::combinators::{And, Each, EachValue, Map, Not, Optional, Or, When};
+
+/// A reusable rule to validate values of type `T`.
+///
+/// Validators record all their errors at the path of the validated value so
+/// combined validators report every failing rule. As the combinators can be
+/// constructed with `const fn`s, rules can be defined once as constants and be
+/// plugged into [`FieldBuilder::validated()`](crate::builder::FieldBuilder::validated),
+/// [`StructBuilder::validated_field()`](crate::StructBuilder::validated_field),
+/// [`ErrorAccumulator::validated_field()`](crate::ErrorAccumulator::validated_field)
+/// and [`ArrayBuilder::of_validated()`](crate::builder::ArrayBuilder::of_validated).
+///
+/// Closures like `|port: &u16| in_range(*port, 1..=1024)` are validators
+/// recording the error they return.
+///
+/// ```
+/// use error_accumulator::{
+///     ErrorAccumulator, field,
+///     validators::{And, Each, InRange, Length, NotEmpty, OneOf, Validator},
+/// };
+///
+/// const NAME: And<NotEmpty, Length> = And::new(NotEmpty, Length::new(0, 16));
+/// const PORTS: Each<InRange<u16>> = Each::new(InRange::new(1, 1024));
+///
+/// let errors = ErrorAccumulator::new()
+///     .validated_field(field!("name"), String::new(), &NAME)
+///     .validated_field(field!("ports"), vec![80, 8080], &PORTS)
+///     .validated_field(field!("scheme"), "ftp", &OneOf(&["http", "https"]))
+///     .analyse()
+///     .unwrap_err();
+///
+/// let paths = errors.iter().map(|(path, _)| path.to_string()).collect::<Vec<_>>();
+/// assert_eq!(paths, ["name", "ports[1]", "scheme"]);
+/// assert!(NAME.check("api").is_ok());
+/// ```
+pub trait Validator<T: ?Sized> {
+    /// Validate `value` found at `path` and record all errors in `errors`.
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError);
+
+    /// Validate `value` on its own, i.e. without an error limit.
+    fn check(&self, value: &T) -> Result<(), AccumulatedError> {
+        let mut errors = AccumulatedError::default();
+        self.validate(value, &SourcePath::new(), &mut errors);
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Require this and the `other` validator to pass.
+    fn and<V>(self, other: V) -> And<Self, V>
+    where
+        Self: Sized,
+        V: Validator<T>,
+    {
+        And::new(self, other)
+    }
+
+    /// Require this or the `other` validator to pass.
+    fn or<V>(self, other: V) -> Or<Self, V>
+    where
+        Self: Sized,
+        V: Validator<T>,
+    {
+        Or::new(self, other)
+    }
+
+    /// Record `error` if this validator passes.
+    fn not<E>(self, error: E) -> Not<Self, E>
+    where
+        Self: Sized,
+        E: Error + Clone + Send + Sync + 'static,
+    {
+        Not::new(self, error)
+    }
+
+    /// Validate the part of a `U` selected by `project`, e.g. a field.
+    fn map<U, F>(self, project: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        U: ?Sized,
+        F: Fn(&U) -> &T,
+    {
+        Map::new(self, project)
+    }
+
+    /// Validate each element of a collection, see [`Each`].
+    fn each(self) -> Each<Self>
+    where
+        Self: Sized,
+    {
+        Each::new(self)
+    }
+
+    /// Validate each value of a map, see [`EachValue`].
+    fn each_value(self) -> EachValue<Self>
+    where
+        Self: Sized,
+    {
+        EachValue::new(self)
+    }
+
+    /// Validate the value of an [`Option`] if it is `Some`.
+    fn optional(self) -> Optional<Self>
+    where
+        Self: Sized,
+    {
+        Optional::new(self)
+    }
+
+    /// Validate only values that match `predicate`.
+    fn when<P>(self, predicate: P) -> When<Self, P>
+    where
+        Self: Sized,
+        P: Fn(&T) -> bool,
+    {
+        When::new(self, predicate)
+    }
+}
+
+/// A [`Validator`] for strings with [`not_empty()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotEmpty;
+
+/// A [`Validator`] for strings with [`length_between()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Length {
+    /// The minimum length in characters.
+    pub min: usize,
+    /// The maximum length in characters.
+    pub max: usize,
+}
+
+/// A [`Validator`] with [`in_range()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InRange<T> {
+    /// The inclusive lower bound.
+    pub min: T,
+    /// The inclusive upper bound.
+    pub max: T,
+}
+
+/// A [`Validator`] for strings with [`one_of()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneOf(pub &'static [&'static str]);
+
+/// A [`Validator`] for strings with [`matches()`].
+///
+/// The regular expression is compiled once, on first use, by the
+/// [`LazyLock`] so the validator can be a constant:
+///
+/// ```
+/// use std::sync::LazyLock;
+///
+/// use error_accumulator::validators::{Matches, Validator};
+/// use regex::Regex;
+///
+/// static SLUG: LazyLock<Regex> = LazyLock::new(|| Regex::new("^[a-z-]+$").unwrap());
+/// const NAME: Matches = Matches(&SLUG);
+///
+/// assert!(NAME.check("my-api").is_ok());
+/// assert!(NAME.check("My API").is_err());
+/// ```
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, Copy)]
+pub struct Matches(pub &'static LazyLock<regex::Regex>);
+
+/// The error of [`not_empty()`].
+///
+/// Its code and message key are `empty`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("must not be empty")]
+pub struct Empty;
+
+/// The error of [`min_length()`] and [`length_between()`] if the value is too
+/// short.
+///
+/// Its code and message key are `too_short` with the arguments `min` and
+/// `actual`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("must be at least {min} characters long but is {actual}")]
+pub struct TooShort {
+    /// The minimum length in characters.
+    pub min: usize,
+    /// The length of the value in characters.
+    pub actual: usize,
+}
+
+/// The error of [`max_length()`] and [`length_between()`] if the value is too
+/// long.
+///
+/// Its code and message key are `too_long` with the arguments `max` and
+/// `actual`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("must be at most {max} characters long but is {actual}")]
+pub struct TooLong {
+    /// The maximum length in characters.
+    pub max: usize,
+    /// The length of the value in characters.
+    pub actual: usize,
+}
+
+/// The error of [`length_between()`].
+///
+/// Its code and message key are the ones of the wrapped error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidLength {
+    /// The value is too short.
+    #[error(transparent)]
+    TooShort(#[from] TooShort),
+    /// The value is too long.
+    #[error(transparent)]
+    TooLong(#[from] TooLong),
+}
+
+/// The error of [`in_range()`].
+///
+/// Its code and message key are `out_of_range` with the arguments `min`, `max`
+/// and `actual`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{actual} is not between {min} and {max}")]
+pub struct OutOfRange<T> {
+    /// The inclusive lower bound.
+    pub min: T,
+    /// The inclusive upper bound.
+    pub max: T,
+    /// The value.
+    pub actual: T,
+}
+
+/// The error of [`one_of()`].
+///
+/// Its code and message key are `not_one_of` with the arguments `actual` and
+/// `allowed`, a comma-separated list.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("'{actual}' is not one of {allowed:?}")]
+pub struct NotOneOf {
+    /// The value.
+    pub actual: String,
+    /// All allowed values.
+    pub allowed: &'static [&'static str],
+}
+
+/// The error of [`matches()`].
+///
+/// Its code and message key are `no_match` with the argument `pattern`.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("does not match the pattern `{pattern}`")]
+pub struct NoMatch {
+    /// The pattern of the regular expression.
+    pub pattern: String,
+}
+
+/// Check that a string is not empty.
+pub fn not_empty<S>(value: S) -> Result<S, Empty>
+where
+    S: AsRef<str>,
+{
+    if value.as_ref().is_empty() {
+        Err(Empty)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Check that a string has at least `min` characters.
+pub fn min_length<S>(value: S, min: usize) -> Result<S, TooShort>
+where
+    S: AsRef<str>,
+{
+    let actual = value.as_ref().chars().count();
+    if actual < min {
+        Err(TooShort { min, actual })
+    } else {
+        Ok(value)
+    }
+}
+
+/// Check that a string has at most `max` characters.
+pub fn max_length<S>(value: S, max: usize) -> Result<S, TooLong>
+where
+    S: AsRef<str>,
+{
+    let actual = value.as_ref().chars().count();
+    if actual > max {
+        Err(TooLong { max, actual })
+    } else {
+        Ok(value)
+    }
+}
+
+/// Check that a string has between `min` and `max` characters, both
+/// inclusive.
+pub fn length_between<S>(value: S, min: usize, max: usize) -> Result<S, InvalidLength>
+where
+    S: AsRef<str>,
+{
+    let value = min_length(value, min)?;
+    Ok(max_length(value, max)?)
+}
+
+/// Check that a value is within the inclusive `range`.
+///
+/// ```
+/// # use error_accumulator::validators::{OutOfRange, in_range};
+/// assert_eq!(in_range(8080, 1..=1024), Err(OutOfRange { min: 1, max: 1024, actual: 8080 }));
+/// ```
+pub fn in_range<T>(value: T, range: RangeInclusive<T>) -> Result<T, OutOfRange<T>>
+where
+    T: PartialOrd,
+{
+    if range.contains(&value) {
+        Ok(value)
+    } else {
+        let (min, max) = range.into_inner();
+        Err(OutOfRange {
+            min,
+            max,
+            actual: value,
+        })
+    }
+}
+
+/// Check that a string is one of the `allowed` values.
+pub fn one_of<S>(value: S, allowed: &'static [&'static str]) -> Result<S, NotOneOf>
+where
+    S: AsRef<str>,
+{
+    if allowed.contains(&value.as_ref()) {
+        Ok(value)
+    } else {
+        Err(NotOneOf {
+            actual: value.as_ref().to_string(),
+            allowed,
+        })
+    }
+}
+
+/// Check that a string matches the regular expression `regex`.
+///
+/// Use [`Regex::is_match()`](regex::Regex::is_match) semantics, i.e. anchor
+/// the pattern with `^` and `$` to match the whole string.
+#[cfg(feature = "regex")]
+pub fn matches<S>(value: S, regex: &regex::Regex) -> Result<S, NoMatch>
+where
+    S: AsRef<str>,
+{
+    if regex.is_match(value.as_ref()) {
+        Ok(value)
+    } else {
+        Err(NoMatch {
+            pattern: regex.as_str().to_string(),
+        })
+    }
+}
+
+impl<T, O, E, F> Validator<T> for F
+where
+    T: ?Sized,
+    E: Error + Send + Sync + 'static,
+    F: Fn(&T) -> Result<O, E>,
+{
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError) {
+        if let Err(error) = self(value) {
+            errors.push(path.clone(), error);
+        }
+    }
+}
+
+impl<S> Validator<S> for NotEmpty
+where
+    S: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &S, path: &SourcePath, errors: &mut AccumulatedError) {
+        if let Err(error) = not_empty(value.as_ref()) {
+            errors.push(path.clone(), Coded::localized(error));
+        }
+    }
+}
+
+impl Length {
+    /// Construct the validator of strings with between `min` and `max`
+    /// characters, both inclusive.
+    pub const fn new(min: usize, max: usize) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<S> Validator<S> for Length
+where
+    S: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &S, path: &SourcePath, errors: &mut AccumulatedError) {
+        if let Err(error) = length_between(value.as_ref(), self.min, self.max) {
+            errors.push(path.clone(), Coded::localized(error));
+        }
+    }
+}
+
+impl<T> InRange<T> {
+    /// Construct the validator of values between `min` and `max`, both
+    /// inclusive.
+    pub const fn new(min: T, max: T) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<T> Validator<T> for InRange<T>
+where
+    T: PartialOrd + Clone + fmt::Debug + fmt::Display + Into<MessageArg> + Send + Sync + 'static,
+{
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError) {
+        let range = self.min.clone()..=self.max.clone();
+        if let Err(error) = in_range(value.clone(), range) {
+            errors.push(path.clone(), Coded::localized(error));
+        }
+    }
+}
+
+impl<S> Validator<S> for OneOf
+where
+    S: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &S, path: &SourcePath, errors: &mut AccumulatedError) {
+        if let Err(error) = one_of(value.as_ref(), self.0) {
+            errors.push(path.clone(), Coded::localized(error));
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl<S> Validator<S> for Matches
+where
+    S: AsRef<str> + ?Sized,
+{
+    fn validate(&self, value: &S, path: &SourcePath, errors: &mut AccumulatedError) {
+        if let Err(error) = matches(value.as_ref(), self.0) {
+            errors.push(path.clone(), Coded::localized(error));
+        }
+    }
+}
+
+/// Validate `value` and return it if `validator` recorded no errors.
+pub(crate) fn validate_value<T, V>(
+    value: T,
+    validator: &V,
+    path: &SourcePath,
+    errors: &mut AccumulatedError,
+) -> Option<T>
+where
+    V: Validator<T> + ?Sized,
+{
+    let mut recorded = AccumulatedError::with_limit(errors.remaining());
+    validator.validate(&value, path, &mut recorded);
+    let valid = !recorded.has_errors();
+    errors.merge(recorded);
+    valid.then_some(value)
+}
+
+impl ErrorCode for Empty {
+    fn code(&self) -> Cow<'static, str> {
+        "empty".into()
+    }
+}
+
+impl Localize for Empty {
+    fn message_key(&self) -> Cow<'static, str> {
+        "empty".into()
+    }
+}
+
+impl ErrorCode for TooShort {
+    fn code(&self) -> Cow<'static, str> {
+        "too_short".into()
+    }
+}
+
+impl Localize for TooShort {
+    fn message_key(&self) -> Cow<'static, str> {
+        "too_short".into()
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new()
+            .with("min", self.min)
+            .with("actual", self.actual)
+    }
+}
+
+impl ErrorCode for TooLong {
+    fn code(&self) -> Cow<'static, str> {
+        "too_long".into()
+    }
+}
+
+impl Localize for TooLong {
+    fn message_key(&self) -> Cow<'static, str> {
+        "too_long".into()
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new()
+            .with("max", self.max)
+            .with("actual", self.actual)
+    }
+}
+
+impl ErrorCode for InvalidLength {
+    fn code(&self) -> Cow<'static, str> {
+        match self {
+            InvalidLength::TooShort(error) => error.code(),
+            InvalidLength::TooLong(error) => error.code(),
+        }
+    }
+}
+
+impl Localize for InvalidLength {
+    fn message_key(&self) -> Cow<'static, str> {
+        match self {
+            InvalidLength::TooShort(error) => error.message_key(),
+            InvalidLength::TooLong(error) => error.message_key(),
+        }
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        match self {
+            InvalidLength::TooShort(error) => error.message_args(),
+            InvalidLength::TooLong(error) => error.message_args(),
+        }
+    }
+}
+
+impl<T> ErrorCode for OutOfRange<T>
+where
+    T: fmt::Debug + fmt::Display,
+{
+    fn code(&self) -> Cow<'static, str> {
+        "out_of_range".into()
+    }
+}
+
+impl<T> Localize for OutOfRange<T>
+where
+    T: fmt::Debug + fmt::Display + Clone + Into<MessageArg>,
+{
+    fn message_key(&self) -> Cow<'static, str> {
+        "out_of_range".into()
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new()
+            .with("min", self.min.clone())
+            .with("max", self.max.clone())
+            .with("actual", self.actual.clone())
+    }
+}
+
+impl ErrorCode for NotOneOf {
+    fn code(&self) -> Cow<'static, str> {
+        "not_one_of".into()
+    }
+}
+
+impl Localize for NotOneOf {
+    fn message_key(&self) -> Cow<'static, str> {
+        "not_one_of".into()
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new()
+            .with("actual", self.actual.clone())
+            .with("allowed", self.allowed.join(", "))
+    }
+}
+
+#[cfg(feature = "regex")]
+impl ErrorCode for NoMatch {
+    fn code(&self) -> Cow<'static, str> {
+        "no_match".into()
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Localize for NoMatch {
+    fn message_key(&self) -> Cow<'static, str> {
+        "no_match".into()
+    }
+
+    fn message_args(&self) -> MessageArgs {
+        MessageArgs::new().with("pattern", self.pattern.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorAccumulator, test_util::n};
+
+    #[test]
+    fn should_check_strings() {
+        assert_eq!(not_empty(""), Err(Empty));
+        assert_eq!(
+            length_between("äöü", 4, 8),
+            Err(InvalidLength::TooShort(TooShort { min: 4, actual: 3 }))
+        );
+        assert_eq!(
+            length_between("äöü", 1, 2),
+            Err(InvalidLength::TooLong(TooLong { max: 2, actual: 3 }))
+        );
+        assert_eq!(length_between("äöü", 3, 3), Ok("äöü"));
+        assert_eq!(one_of("web", &["api", "web"]), Ok("web"));
+        assert_eq!(
+            one_of("ftp", &["http", "https"]).unwrap_err().to_string(),
+            r#"'ftp' is not one of ["http", "https"]"#
+        );
+    }
+
+    #[test]
+    fn should_report_every_failing_check() {
+        let name = "x".repeat(20);
+        let port = 0;
+        let errors = ErrorAccumulator::new()
+            .field_builder(n("name"))
+            .localized_value(max_length(name.as_str(), 16))
+            .check_localized(one_of(name.as_str(), &["api", "web"]))
+            .on_ok(|name: &str| name.to_string())
+            .finish()
+            .localized_field(n("port"), in_range(port, 1..=1024))
+            .analyse()
+            .unwrap_err();
+
+        let messages = errors
+            .iter()
+            .map(|(path, error)| format!("{path}: {error}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "name: must be at most 16 characters long but is 20",
+                r#"name: 'xxxxxxxxxxxxxxxxxxxx' is not one of ["api", "web"]"#,
+                "port: 0 is not between 1 and 1024",
+            ]
+        );
+        assert_eq!(errors.get_by_code("too_long").count(), 1);
+        assert_eq!(errors.get_by_code("not_one_of").count(), 1);
+        assert_eq!(errors.get_by_code("out_of_range").count(), 1);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn should_match_regex() {
+        let regex = regex::Regex::new("^[a-z]+$").unwrap();
+
+        assert_eq!(matches("api", &regex), Ok("api"));
+        assert_eq!(
+            matches("API", &regex).unwrap_err().to_string(),
+            "does not match the pattern `^[a-z]+$`"
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn should_validate_with_constant_regex() {
+        static LOWERCASE: LazyLock<regex::Regex> =
+            LazyLock::new(|| regex::Regex::new("^[a-z]+$").unwrap());
+        const NAME: Matches = Matches(&LOWERCASE);
+
+        assert!(NAME.check("api").is_ok());
+        let errors = NAME.check("API").unwrap_err();
+        assert_eq!(errors.get_by_code("no_match").count(), 1);
+    }
+}
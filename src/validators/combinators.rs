@@ -0,0 +1,463 @@
+//! Combinators of [`Validator`]s.
+
+use std::{error::Error, fmt::Display};
+
+use super::Validator;
+use crate::{
+    error::AccumulatedError,
+    path::{FieldName, PathSegment, SourcePath},
+};
+
+/// Requires both validators to pass and records the errors of both.
+///
+/// See [`Validator::and()`].
+#[derive(Debug, Clone, Copy)]
+pub struct And<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Requires one of the validators to pass and records the errors of both
+/// otherwise.
+///
+/// See [`Validator::or()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Records `error` if the validator passes.
+///
+/// See [`Validator::not()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Not<V, E> {
+    validator: V,
+    error: E,
+}
+
+/// Validates a part of a value.
+///
+/// See [`Validator::map()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Map<V, F> {
+    validator: V,
+    project: F,
+}
+
+/// Validates each element of a collection at the path of the element, e.g.
+/// `hosts[3]`.
+///
+/// Collections are all types whose references iterate over references of
+/// their elements, like slices, arrays, [`Vec`],
+/// [`VecDeque`](std::collections::VecDeque) or
+/// [`BTreeSet`](std::collections::BTreeSet). The remaining elements are
+/// skipped once the [error limit](crate::ErrorAccumulator::max_errors) is
+/// reached.
+///
+/// See [`Validator::each()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Each<V> {
+    validator: V,
+}
+
+/// Validates each value of a map at the path of its key, e.g. `hosts["api"]`.
+///
+/// Maps are all types whose references iterate over pairs of references to
+/// keys and values, like [`HashMap`](std::collections::HashMap) or
+/// [`BTreeMap`](std::collections::BTreeMap). The remaining entries are skipped
+/// once the [error limit](crate::ErrorAccumulator::max_errors) is reached.
+///
+/// See [`Validator::each_value()`].
+#[derive(Debug, Clone, Copy)]
+pub struct EachValue<V> {
+    validator: V,
+}
+
+/// Validates the value of an [`Option`] if it is `Some`.
+///
+/// See [`Validator::optional()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Optional<V> {
+    validator: V,
+}
+
+/// Validates only values that match a predicate.
+///
+/// See [`Validator::when()`].
+#[derive(Debug, Clone, Copy)]
+pub struct When<V, P> {
+    validator: V,
+    predicate: P,
+}
+
+impl<A, B> And<A, B> {
+    /// Construct the conjunction of `first` and `second`.
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A, B> Or<A, B> {
+    /// Construct the disjunction of `first` and `second`.
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<V, E> Not<V, E> {
+    /// Construct the negation of `validator` that records `error`.
+    pub const fn new(validator: V, error: E) -> Self {
+        Self { validator, error }
+    }
+}
+
+impl<V, F> Map<V, F> {
+    /// Validate the part of a value selected by `project`.
+    pub const fn new(validator: V, project: F) -> Self {
+        Self { validator, project }
+    }
+}
+
+impl<V> Each<V> {
+    /// Validate each element with `validator`.
+    pub const fn new(validator: V) -> Self {
+        Self { validator }
+    }
+}
+
+impl<V> EachValue<V> {
+    /// Validate each value with `validator`.
+    pub const fn new(validator: V) -> Self {
+        Self { validator }
+    }
+}
+
+impl<V> Optional<V> {
+    /// Validate present values with `validator`.
+    pub const fn new(validator: V) -> Self {
+        Self { validator }
+    }
+}
+
+impl<V, P> When<V, P> {
+    /// Validate values that match `predicate` with `validator`.
+    pub const fn new(validator: V, predicate: P) -> Self {
+        Self {
+            validator,
+            predicate,
+        }
+    }
+}
+
+impl<T, A, B> Validator<T> for And<A, B>
+where
+    T: ?Sized,
+    A: Validator<T>,
+    B: Validator<T>,
+{
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError) {
+        self.first.validate(value, path, errors);
+        self.second.validate(value, path, errors);
+    }
+}
+
+impl<T, A, B> Validator<T> for Or<A, B>
+where
+    T: ?Sized,
+    A: Validator<T>,
+    B: Validator<T>,
+{
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError) {
+        let mut first = AccumulatedError::with_limit(errors.remaining());
+        self.first.validate(value, path, &mut first);
+        if !first.has_errors() {
+            errors.merge(first);
+            return;
+        }
+        let mut second = AccumulatedError::with_limit(errors.remaining());
+        self.second.validate(value, path, &mut second);
+        if !second.has_errors() {
+            errors.merge(second);
+            return;
+        }
+        errors.merge(first);
+        errors.merge(second);
+    }
+}
+
+impl<T, V, E> Validator<T> for Not<V, E>
+where
+    T: ?Sized,
+    V: Validator<T>,
+    E: Error + Clone + Send + Sync + 'static,
+{
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError) {
+        let mut inner = AccumulatedError::with_limit(errors.remaining());
+        self.validator.validate(value, path, &mut inner);
+        if !inner.has_errors() {
+            errors.push(path.clone(), self.error.clone());
+        }
+    }
+}
+
+impl<T, U, V, F> Validator<U> for Map<V, F>
+where
+    T: ?Sized,
+    U: ?Sized,
+    V: Validator<T>,
+    F: Fn(&U) -> &T,
+{
+    fn validate(&self, value: &U, path: &SourcePath, errors: &mut AccumulatedError) {
+        self.validator.validate((self.project)(value), path, errors);
+    }
+}
+
+impl<C, T, V> Validator<C> for Each<V>
+where
+    C: ?Sized,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+    V: Validator<T>,
+{
+    fn validate(&self, value: &C, path: &SourcePath, errors: &mut AccumulatedError) {
+        for (index, element) in value.into_iter().enumerate() {
+            if errors.is_exhausted() {
+                errors.set_truncated();
+                break;
+            }
+            self.validator
+                .validate(element, &element_path(path, index), errors);
+        }
+    }
+}
+
+impl<C, K, T, V> Validator<C> for EachValue<V>
+where
+    C: ?Sized,
+    K: Display,
+    for<'a> &'a C: IntoIterator<Item = (&'a K, &'a T)>,
+    V: Validator<T>,
+{
+    fn validate(&self, value: &C, path: &SourcePath, errors: &mut AccumulatedError) {
+        for (key, value) in value {
+            if errors.is_exhausted() {
+                errors.set_truncated();
+                break;
+            }
+            self.validator
+                .validate(value, &entry_path(path, key), errors);
+        }
+    }
+}
+
+impl<T, V> Validator<Option<T>> for Optional<V>
+where
+    V: Validator<T>,
+{
+    fn validate(&self, value: &Option<T>, path: &SourcePath, errors: &mut AccumulatedError) {
+        if let Some(value) = value {
+            self.validator.validate(value, path, errors);
+        }
+    }
+}
+
+impl<T, V, P> Validator<T> for When<V, P>
+where
+    T: ?Sized,
+    V: Validator<T>,
+    P: Fn(&T) -> bool,
+{
+    fn validate(&self, value: &T, path: &SourcePath, errors: &mut AccumulatedError) {
+        if (self.predicate)(value) {
+            self.validator.validate(value, path, errors);
+        }
+    }
+}
+
+/// The path of an element with the same segments as the ones of
+/// [`ArrayBuilder`](crate::builder::ArrayBuilder), i.e. the field `hosts`
+/// becomes the array segment `hosts[3]`.
+fn element_path(path: &SourcePath, index: usize) -> SourcePath {
+    let mut path = path.clone();
+    match path.pop() {
+        Some(PathSegment::Field(name)) => path.push(PathSegment::array(name, index)),
+        Some(segment) => {
+            path.push(segment);
+            path.push(PathSegment::index(index));
+        }
+        None => path.push(PathSegment::index(index)),
+    }
+    path
+}
+
+/// The path of a map value with the same segments as the ones of
+/// [`MapBuilder`](crate::builder::MapBuilder), i.e. the field `hosts` becomes
+/// the key segment `hosts["api"]`.
+///
+/// Maps without a field name, e.g. at the root, use the key as field name.
+fn entry_path(path: &SourcePath, key: &impl Display) -> SourcePath {
+    let mut path = path.clone();
+    match path.pop() {
+        Some(PathSegment::Field(name)) => path.push(PathSegment::key(name, key.to_string())),
+        Some(segment) => {
+            path.push(segment);
+            path.push(PathSegment::field(FieldName::new(key.to_string())));
+        }
+        None => path.push(PathSegment::field(FieldName::new(key.to_string()))),
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+    use super::*;
+    use crate::{
+        ErrorAccumulator,
+        test_util::n,
+        validators::{InRange, Length, NotEmpty, OneOf, in_range},
+    };
+
+    #[derive(Debug)]
+    struct Host {
+        url: String,
+        port: Option<u16>,
+    }
+
+    const URL: And<NotEmpty, Or<OneOf, Length>> = And::new(
+        NotEmpty,
+        Or::new(OneOf(&["localhost"]), Length::new(10, 64)),
+    );
+    const PORT: Optional<InRange<u16>> = Optional::new(InRange::new(1, 1024));
+
+    fn messages(errors: &AccumulatedError) -> Vec<String> {
+        errors
+            .iter()
+            .map(|(path, error)| format!("{path}: {error}"))
+            .collect()
+    }
+
+    #[test]
+    fn should_combine_validators() {
+        let errors = URL.check("").unwrap_err();
+        assert_eq!(
+            messages(&errors),
+            [
+                "root: must not be empty",
+                r#"root: '' is not one of ["localhost"]"#,
+                "root: must be at least 10 characters long but is 0",
+            ]
+        );
+        assert!(URL.check("localhost").is_ok());
+        assert!(URL.check("example.com").is_ok());
+
+        let reserved = InRange::new(1, 1023).not(std::fmt::Error);
+        assert!(reserved.check(&8080).is_ok());
+        assert!(reserved.check(&80).is_err());
+
+        let even = (|port: &u16| in_range(port % 2, 0..=0)).when(|port: &u16| *port > 1024);
+        assert!(even.check(&81).is_ok());
+        assert!(even.check(&8081).is_err());
+    }
+
+    #[test]
+    fn should_validate_each_element() {
+        let hosts = Each::new(
+            URL.map(|host: &Host| host.url.as_str())
+                .and(PORT.map(|host: &Host| &host.port)),
+        );
+
+        let errors = ErrorAccumulator::new()
+            .validated_field(
+                n("hosts"),
+                vec![
+                    Host {
+                        url: "localhost".to_string(),
+                        port: None,
+                    },
+                    Host {
+                        url: "localhost".to_string(),
+                        port: Some(8080),
+                    },
+                ],
+                &hosts,
+            )
+            .strukt(n("db"))
+            .array(n("ports"))
+            .of_validated([80, 0, 8080], &InRange::new(1, 1024))
+            .finish()
+            .on_ok(|ports| ports)
+            .finish()
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(
+            messages(&errors),
+            [
+                "hosts[1]: 8080 is not between 1 and 1024",
+                "db.ports[1]: 0 is not between 1 and 1024",
+                "db.ports[2]: 8080 is not between 1 and 1024",
+            ]
+        );
+        assert_eq!(errors.get_by_code("out_of_range").count(), 3);
+    }
+
+    #[test]
+    fn should_validate_any_collection() {
+        const PORTS: Each<InRange<u16>> = Each::new(InRange::new(1, 1024));
+        const HOSTS: EachValue<InRange<u16>> = EachValue::new(InRange::new(1, 1024));
+        let paths = |errors: AccumulatedError| {
+            errors
+                .iter()
+                .map(|(path, _)| path.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let errors = ErrorAccumulator::new()
+            .validated_field(n("deque"), VecDeque::from([80, 0]), &PORTS)
+            .validated_field(n("set"), BTreeSet::from([0, 80, 8080]), &PORTS)
+            .validated_field(n("array"), [8080], &PORTS)
+            .validated_field(
+                n("hosts"),
+                BTreeMap::from([("api", 80), ("web", 0)]),
+                &HOSTS,
+            )
+            .validated_field(n("ports"), HashMap::from([("admin", 8080)]), &HOSTS)
+            .analyse()
+            .unwrap_err();
+        assert_eq!(
+            paths(errors),
+            [
+                "deque[1]",
+                "set[0]",
+                "set[2]",
+                "array[0]",
+                r#"hosts["web"]"#,
+                r#"ports["admin"]"#,
+            ]
+        );
+
+        assert!(PORTS.check(&vec![80]).is_ok());
+        let errors = HOSTS.check(&BTreeMap::from([("api", 0)])).unwrap_err();
+        assert_eq!(paths(errors), ["api"]);
+    }
+
+    #[test]
+    fn should_stop_alternatives_after_max_errors() {
+        let ids = Or::new(
+            Each::new(InRange::new(1, 10)),
+            Each::new(InRange::new(100, 110)),
+        );
+
+        let errors = ErrorAccumulator::new()
+            .max_errors(2)
+            .validated_field(n("ids"), vec![0; 100_000], &ids)
+            .analyse()
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.suppressed(), 2);
+        assert!(errors.is_truncated());
+    }
+}